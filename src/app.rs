@@ -0,0 +1,459 @@
+use crate::canvas::Canvas;
+use crate::GameState;
+use crate::controls::{KeyBindings, MouseLook};
+use crate::leaderboard::{self, Leaderboard};
+use crate::game::InputFrame;
+use crate::level::LevelSource;
+use crate::render::render_3d_basic;
+use crate::screens;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use winit::event::VirtualKeyCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameScene {
+    Welcome,
+    Leaderboard,
+    Playing,
+    Paused,
+    Success,
+}
+
+// Opciones del menú de pausa, en el orden en que se muestran
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseOption {
+    Resume,
+    Restart,
+    Quit,
+}
+
+impl PauseOption {
+    pub const ALL: [PauseOption; 3] = [PauseOption::Resume, PauseOption::Restart, PauseOption::Quit];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PauseOption::Resume => "Reanudar",
+            PauseOption::Restart => "Reiniciar nivel",
+            PauseOption::Quit => "Salir",
+        }
+    }
+}
+
+const NOTICE_DURATION: Duration = Duration::from_secs(2);
+
+// Resultado de procesar una tecla
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Continue,
+    Quit,
+}
+
+pub struct App {
+    pub scene: GameScene,
+    pub state: GameState,
+    pub level: LevelSource,
+    optimal_steps: Option<usize>,
+    pub show_fps: bool,
+    pub keys: KeyBindings,
+    pub mouse: MouseLook,
+    leaderboard: Leaderboard,
+    map_key: String,
+    name_entry: Option<String>, // nombre en edición tras un nuevo récord
+    stale_keys: HashSet<VirtualKeyCode>, // teclas ya pulsadas al abrir el nombre
+    saved_rank: Option<usize>,
+    pause_selected: usize,
+    held: HashSet<VirtualKeyCode>,
+    pending_look: f32, // giro del mouse aún no simulado
+    screenshot_requested: bool,
+    notice: Option<(String, Instant)>, // aviso breve en pantalla y cuándo se mostró
+}
+
+impl App {
+    pub fn new(state: GameState, level: LevelSource, keys: KeyBindings, mouse: MouseLook) -> Self {
+        let optimal_steps = state.game.map.shortest_path().map(|path| path.steps());
+        let map_key = leaderboard::map_key(&state.game.map);
+        Self {
+            scene: GameScene::Welcome,
+            state,
+            level,
+            optimal_steps,
+            show_fps: true,
+            keys,
+            mouse,
+            leaderboard: Leaderboard::load(),
+            map_key,
+            name_entry: None,
+            stale_keys: HashSet::new(),
+            saved_rank: None,
+            pause_selected: 0,
+            held: HashSet::new(),
+            pending_look: 0.0,
+            screenshot_requested: false,
+            notice: None,
+        }
+    }
+
+    // Cambia de escena ejecutando la salida de la actual y la entrada de la nueva
+    pub fn transition(&mut self, next: GameScene) {
+        let previous = self.scene;
+        if previous == next {
+            return;
+        }
+        self.exit(previous);
+        self.scene = next;
+        self.enter(next, previous);
+    }
+
+    fn enter(&mut self, scene: GameScene, from: GameScene) {
+        match scene {
+            GameScene::Playing => {
+                // La partida arranca al comenzar a jugar, no al abrir el programa
+                if from == GameScene::Welcome {
+                    self.state.game.reset();
+                }
+                self.update_step_sound();
+            }
+            GameScene::Paused => {
+                self.pause_selected = 0;
+                self.state.audio_manager.pause();
+            }
+            GameScene::Success => {
+                let time = self.state.game.elapsed();
+                self.saved_rank = None;
+                self.name_entry = self.leaderboard.qualifies(&self.map_key, time).then(String::new);
+                // Una tecla que se mantiene desde la partida no debe escribir en el nombre
+                self.stale_keys = self.held.clone();
+            }
+            _ => {}
+        }
+    }
+
+    fn exit(&mut self, scene: GameScene) {
+        match scene {
+            GameScene::Playing => {
+                // Se detienen los pasos; la simulación solo avanza mientras se juega
+                self.pending_look = 0.0;
+                self.state.audio_manager.stop_running_loop();
+            }
+            GameScene::Paused => self.state.audio_manager.resume(),
+            _ => {}
+        }
+    }
+
+    // Devuelve al jugador a la entrada con el cronómetro en cero
+    pub fn reset_run(&mut self) {
+        self.state.game.reset();
+    }
+
+    // Carga otro laberinto y comienza a jugarlo; si falla se repite el actual
+    pub fn load_level(&mut self, source: LevelSource) {
+        match source.load() {
+            Ok(map) => {
+                self.optimal_steps = map.shortest_path().map(|path| path.steps());
+                self.map_key = leaderboard::map_key(&map);
+                self.state.game.map = map;
+                self.level = source;
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+        self.reset_run();
+        self.transition(GameScene::Playing);
+    }
+
+    // Sonido de pasos mientras se camina en cualquier dirección
+    fn update_step_sound(&mut self) {
+        let k = self.keys;
+        let moving = [k.forward, k.back, k.strafe_left, k.strafe_right].iter().any(|key| self.held.contains(key));
+        if moving {
+            self.state.audio_manager.play_running_loop();
+        } else {
+            self.state.audio_manager.stop_running_loop();
+        }
+    }
+
+    pub fn handle_key(&mut self, key: VirtualKeyCode, pressed: bool) -> Command {
+        // Se ignoran las repeticiones automáticas de una tecla mantenida
+        let repeat = pressed && self.held.contains(&key);
+        if pressed {
+            self.held.insert(key);
+        } else {
+            self.held.remove(&key);
+            self.stale_keys.remove(&key);
+        }
+        if repeat {
+            return Command::Continue;
+        }
+        // F12 captura la pantalla en cualquier escena
+        if pressed && key == VirtualKeyCode::F12 {
+            self.screenshot_requested = true;
+            return Command::Continue;
+        }
+
+        match self.scene {
+            GameScene::Welcome => self.welcome_input(key, pressed),
+            GameScene::Leaderboard => self.leaderboard_input(key, pressed),
+            GameScene::Playing => self.playing_input(key, pressed),
+            GameScene::Paused => self.paused_input(key, pressed),
+            GameScene::Success => self.success_input(key, pressed),
+        }
+    }
+
+    fn welcome_input(&mut self, key: VirtualKeyCode, pressed: bool) -> Command {
+        match key {
+            VirtualKeyCode::Escape if pressed => return Command::Quit,
+            VirtualKeyCode::Space if pressed => self.transition(GameScene::Playing),
+            VirtualKeyCode::L if pressed => self.transition(GameScene::Leaderboard),
+            _ => {}
+        }
+        Command::Continue
+    }
+
+    fn leaderboard_input(&mut self, key: VirtualKeyCode, pressed: bool) -> Command {
+        if pressed && matches!(key, VirtualKeyCode::Escape | VirtualKeyCode::L | VirtualKeyCode::Back) {
+            self.transition(GameScene::Welcome);
+        }
+        Command::Continue
+    }
+
+    fn playing_input(&mut self, key: VirtualKeyCode, pressed: bool) -> Command {
+        match key {
+            VirtualKeyCode::Escape | VirtualKeyCode::P if pressed => self.transition(GameScene::Paused),
+            VirtualKeyCode::F if pressed => self.show_fps = !self.show_fps,
+            VirtualKeyCode::M if pressed => self.mouse.enabled = !self.mouse.enabled,
+            VirtualKeyCode::LBracket if pressed => self.mouse.scale_sensitivity(0.8),
+            VirtualKeyCode::RBracket if pressed => self.mouse.scale_sensitivity(1.25),
+            k if k == self.keys.forward || k == self.keys.back || k == self.keys.strafe_left || k == self.keys.strafe_right => {
+                self.update_step_sound()
+            }
+            _ => {}
+        }
+        Command::Continue
+    }
+
+    // Movimiento relativo del mouse (en píxeles) mientras está activa la vista con mouse
+    pub fn handle_mouse_motion(&mut self, dx: f64) {
+        if self.scene == GameScene::Playing && self.mouse.enabled {
+            self.pending_look += dx as f32 * self.mouse.sensitivity;
+        }
+    }
+
+    // Indica (una sola vez) que se pidió una captura del próximo cuadro
+    pub fn take_screenshot_request(&mut self) -> bool {
+        std::mem::take(&mut self.screenshot_requested)
+    }
+
+    pub fn notify(&mut self, text: String) {
+        self.notice = Some((text, Instant::now()));
+    }
+
+    // Aviso vigente, si todavía no pasó su tiempo en pantalla
+    pub fn notice(&self) -> Option<&str> {
+        self.notice
+            .as_ref()
+            .filter(|(_, since)| since.elapsed() < NOTICE_DURATION)
+            .map(|(text, _)| text.as_str())
+    }
+
+    // El cursor se captura solo mientras se juega con la vista con mouse
+    pub fn wants_cursor_grab(&self) -> bool {
+        self.scene == GameScene::Playing && self.mouse.enabled
+    }
+
+    fn paused_input(&mut self, key: VirtualKeyCode, pressed: bool) -> Command {
+        if !pressed {
+            return Command::Continue;
+        }
+        let count = PauseOption::ALL.len();
+        match key {
+            VirtualKeyCode::Escape | VirtualKeyCode::P => self.transition(GameScene::Playing),
+            k if k == VirtualKeyCode::Up || k == self.keys.forward => {
+                self.pause_selected = (self.pause_selected + count - 1) % count;
+            }
+            k if k == VirtualKeyCode::Down || k == self.keys.back => {
+                self.pause_selected = (self.pause_selected + 1) % count;
+            }
+            VirtualKeyCode::Return | VirtualKeyCode::Space => match PauseOption::ALL[self.pause_selected] {
+                PauseOption::Resume => self.transition(GameScene::Playing),
+                PauseOption::Restart => {
+                    self.reset_run();
+                    self.transition(GameScene::Playing);
+                }
+                PauseOption::Quit => return Command::Quit,
+            },
+            _ => {}
+        }
+        Command::Continue
+    }
+
+    // Texto escrito (solo se usa al ingresar el nombre de un récord)
+    pub fn handle_char(&mut self, c: char) {
+        // Se descarta hasta soltar las teclas que venían pulsadas de la partida
+        if self.scene != GameScene::Success || !self.stale_keys.is_empty() {
+            return;
+        }
+        if let Some(name) = &mut self.name_entry {
+            if !c.is_control() && name.chars().count() < leaderboard::MAX_NAME_LEN {
+                name.push(c);
+            }
+        }
+    }
+
+    fn success_input(&mut self, key: VirtualKeyCode, pressed: bool) -> Command {
+        if !pressed {
+            return Command::Continue;
+        }
+        if let Some(name) = &mut self.name_entry {
+            match key {
+                VirtualKeyCode::Back => {
+                    name.pop();
+                }
+                VirtualKeyCode::Return => {
+                    let time = self.state.game.stats.finished_time.unwrap_or_default();
+                    self.saved_rank = self.leaderboard.record(&self.map_key, name, time);
+                    self.name_entry = None;
+                    if let Err(e) = self.leaderboard.save() {
+                        eprintln!("No se pudo guardar la tabla de récords: {}", e);
+                    }
+                }
+                // Se descarta el récord sin guardarlo
+                VirtualKeyCode::Escape => self.name_entry = None,
+                _ => {}
+            }
+            return Command::Continue;
+        }
+        match key {
+            // Terminar el juego
+            VirtualKeyCode::Escape => return Command::Quit,
+            VirtualKeyCode::R => {
+                self.reset_run();
+                self.transition(GameScene::Playing);
+            }
+            VirtualKeyCode::N => self.load_level(self.level.next()),
+            VirtualKeyCode::G => self.load_level(self.level.random()),
+            _ => {}
+        }
+        Command::Continue
+    }
+
+    // Convierte las teclas mantenidas en entrada y avanza la simulación con
+    // el tiempo transcurrido desde el cuadro anterior
+    pub fn update(&mut self, dt: f32) {
+        if self.scene != GameScene::Playing {
+            return;
+        }
+        let held = |key| self.held.contains(&key);
+        let axis = |pos, neg| (held(pos) as i8 - held(neg) as i8) as f32;
+        let input = InputFrame {
+            forward: axis(self.keys.forward, self.keys.back),
+            strafe: axis(self.keys.strafe_right, self.keys.strafe_left),
+            turn: axis(self.keys.turn_right, self.keys.turn_left),
+            look: self.pending_look,
+        };
+        if self.state.game.advance(input, dt) > 0 {
+            self.pending_look = 0.0;
+        }
+        if self.state.game.is_finished() {
+            self.transition(GameScene::Success);
+        }
+    }
+
+    pub fn render(&self, canvas: &mut Canvas) {
+        let text = &self.state.text;
+        match self.scene {
+            GameScene::Welcome => screens::show_welcome_screen(canvas, text, &self.state.images),
+            GameScene::Leaderboard => {
+                let rows: Vec<String> = self
+                    .leaderboard
+                    .top(&self.map_key)
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| format!("{:>2}. {:<16} {}", i + 1, e.name, leaderboard::format_time(e.time_ms)))
+                    .collect();
+                screens::show_leaderboard(canvas, text, &rows);
+            }
+            GameScene::Playing => self.render_view(canvas),
+            GameScene::Paused => {
+                self.render_view(canvas);
+                let entries = PauseOption::ALL.map(PauseOption::label);
+                screens::show_pause_menu(canvas, text, &entries, self.pause_selected);
+            }
+            GameScene::Success => {
+                let mut summary = self.state.game.stats.summary(self.optimal_steps);
+                if let Some(name) = &self.name_entry {
+                    summary.push(format!("¡Nuevo récord! Nombre: {}_", name));
+                    summary.push("ENTER para guardar, ESC para omitir".to_string());
+                } else if let Some(rank) = self.saved_rank {
+                    summary.push(format!("Récord guardado en el puesto #{}", rank));
+                }
+                screens::show_success_screen(canvas, text, &self.state.images, &summary);
+            }
+        }
+    }
+
+    fn render_view(&self, canvas: &mut Canvas) {
+        render_3d_basic(canvas.pixels, canvas.width, canvas.height, &self.state.game, &self.state.textures);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{App, GameScene};
+    use crate::controls::{KeyBindings, MouseLook, DEFAULT_SENSITIVITY};
+    use crate::leaderboard::Leaderboard;
+    use crate::level::LevelSource;
+    use crate::{audio, image_cache, text, GameState};
+    use proyecto::assets::Assets;
+    use proyecto::{game, map, textures};
+    use winit::event::VirtualKeyCode;
+
+    fn test_app() -> App {
+        let mut assets = Assets::default();
+        let state = GameState {
+            game: game::Game::new(map::Map::new()),
+            audio_manager: audio::AudioManager::new(&assets),
+            textures: textures::TextureAtlas::default(),
+            text: text::TextRenderer::bitmap(),
+            images: image_cache::ImageCache::new(&mut assets),
+        };
+        let mut app = App::new(state, LevelSource::Builtin, KeyBindings::default(), MouseLook::new(false, DEFAULT_SENSITIVITY));
+        app.leaderboard = Leaderboard::load_from(std::env::temp_dir().join("laberinto3d-app-test-no-existe.tsv"));
+        app
+    }
+
+    #[test]
+    fn held_key_does_not_type_into_record_name() {
+        let mut app = test_app();
+        app.transition(GameScene::Playing);
+        app.handle_key(VirtualKeyCode::W, true);
+        app.transition(GameScene::Success);
+
+        // Repeticiones automáticas de W, que venía pulsada desde la partida
+        for _ in 0..5 {
+            app.handle_key(VirtualKeyCode::W, true);
+            app.handle_char('w');
+        }
+        assert_eq!(app.name_entry.as_deref(), Some(""));
+
+        app.handle_key(VirtualKeyCode::W, false);
+        app.handle_key(VirtualKeyCode::A, true);
+        app.handle_char('a');
+        assert_eq!(app.name_entry.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn held_pause_key_toggles_once_per_press() {
+        let mut app = test_app();
+        app.transition(GameScene::Playing);
+        app.handle_key(VirtualKeyCode::P, true);
+        assert_eq!(app.scene, GameScene::Paused);
+
+        // Las repeticiones de P mantenida no vuelven a cambiar de escena
+        app.handle_key(VirtualKeyCode::P, true);
+        app.handle_key(VirtualKeyCode::P, true);
+        assert_eq!(app.scene, GameScene::Paused);
+
+        app.handle_key(VirtualKeyCode::P, false);
+        app.handle_key(VirtualKeyCode::P, true);
+        assert_eq!(app.scene, GameScene::Playing);
+    }
+}
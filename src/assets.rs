@@ -0,0 +1,343 @@
+use image::RgbaImage;
+use rusttype::Font;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// Variable de entorno con la carpeta de recursos
+pub const ENV_VAR: &str = "LABERINTO_ASSETS";
+const DIR_NAME: &str = "assets";
+
+// Con la característica `embedded-assets` el ejecutable lleva una copia de
+// `assets/` y no necesita la carpeta para funcionar
+pub const EMBEDDED: bool = cfg!(feature = "embedded-assets");
+
+#[cfg(feature = "embedded-assets")]
+macro_rules! embed {
+    ($($name:literal),* $(,)?) => {
+        &[$(($name, include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/", $name)))),*]
+    };
+}
+
+#[cfg(feature = "embedded-assets")]
+const EMBEDDED_FILES: &[(&str, &[u8])] = embed!(
+    "background_music.mp3",
+    "step.mp3",
+    "welcome.png",
+    "success_image.png",
+    "wall_1.png",
+    "wall_2.png",
+    "wall_3.png",
+    "floor.png",
+    "ceiling.png",
+    "DejaVuSans.ttf",
+);
+
+#[cfg(feature = "embedded-assets")]
+fn embedded(file_name: &str) -> Option<&'static [u8]> {
+    EMBEDDED_FILES.iter().find(|(name, _)| *name == file_name).map(|(_, bytes)| *bytes)
+}
+
+#[cfg(not(feature = "embedded-assets"))]
+fn embedded(_file_name: &str) -> Option<&'static [u8]> {
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundId {
+    Music,
+    Step,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageId {
+    Welcome,
+    Success,
+    Wall1,
+    Wall2,
+    Wall3,
+    Floor,
+    Ceiling,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FontId {
+    Ui,
+}
+
+impl SoundId {
+    pub const ALL: [SoundId; 2] = [SoundId::Music, SoundId::Step];
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            SoundId::Music => "background_music.mp3",
+            SoundId::Step => "step.mp3",
+        }
+    }
+}
+
+impl ImageId {
+    pub const ALL: [ImageId; 7] = [
+        ImageId::Welcome,
+        ImageId::Success,
+        ImageId::Wall1,
+        ImageId::Wall2,
+        ImageId::Wall3,
+        ImageId::Floor,
+        ImageId::Ceiling,
+    ];
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ImageId::Welcome => "welcome.png",
+            ImageId::Success => "success_image.png",
+            ImageId::Wall1 => "wall_1.png",
+            ImageId::Wall2 => "wall_2.png",
+            ImageId::Wall3 => "wall_3.png",
+            ImageId::Floor => "floor.png",
+            ImageId::Ceiling => "ceiling.png",
+        }
+    }
+
+    // Textura de cada valor de celda de `Map.grid`
+    pub fn wall(wall_type: u8) -> Option<ImageId> {
+        match wall_type {
+            1 => Some(ImageId::Wall1),
+            2 => Some(ImageId::Wall2),
+            3 => Some(ImageId::Wall3),
+            _ => None,
+        }
+    }
+}
+
+impl FontId {
+    pub const ALL: [FontId; 1] = [FontId::Ui];
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            FontId::Ui => "DejaVuSans.ttf",
+        }
+    }
+}
+
+// Recurso que no se pudo cargar
+#[derive(Debug)]
+pub struct AssetError {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.reason)
+    }
+}
+
+// Por qué no se pudo elegir la carpeta de recursos
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirError {
+    // `--assets` o la variable de entorno indican una carpeta que no existe
+    Missing { dir: PathBuf, source: &'static str },
+    // La búsqueda automática no encontró ninguna carpeta `assets/`
+    NotFound(Vec<PathBuf>),
+}
+
+impl fmt::Display for DirError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DirError::Missing { dir, source } => {
+                write!(f, "La carpeta de recursos '{}' indicada con {} no existe", dir.display(), source)
+            }
+            DirError::NotFound(searched) => {
+                let searched: Vec<String> = searched.iter().map(|dir| format!("'{}'", dir.display())).collect();
+                write!(f, "No se encontró la carpeta de recursos (se buscó en {})", searched.join(" y "))
+            }
+        }
+    }
+}
+
+// Todos los recursos del juego, leídos una sola vez al iniciar. Los sonidos
+// se guardan sin decodificar; las imágenes y la fuente ya decodificadas
+#[derive(Default)]
+pub struct Assets {
+    sounds: HashMap<SoundId, Arc<[u8]>>,
+    images: HashMap<ImageId, RgbaImage>,
+    fonts: HashMap<FontId, Font<'static>>,
+}
+
+impl Assets {
+    // Carpeta de recursos: la de `--assets`, la de la variable de entorno,
+    // la que está junto al ejecutable o la del directorio actual, en ese orden
+    pub fn resolve_dir(flag: Option<&Path>) -> Result<PathBuf, DirError> {
+        let exe_dir = std::env::current_exe().ok().and_then(|exe| exe.parent().map(Path::to_path_buf));
+        let cwd = std::env::current_dir().unwrap_or_default();
+        resolve_from(flag, std::env::var_os(ENV_VAR), exe_dir, cwd)
+    }
+
+    // Carga cada recurso de `dir` o, si no está ahí, de la copia integrada;
+    // lo que no se pueda cargar se informa y el juego sigue sin ese recurso
+    pub fn load(dir: Option<&Path>) -> (Self, Vec<AssetError>) {
+        let mut assets = Self::default();
+        let mut errors = Vec::new();
+        for id in SoundId::ALL {
+            if let Some(sound) = load_file(dir, id.file_name(), &mut errors, |bytes| Ok(Arc::from(&*bytes))) {
+                assets.sounds.insert(id, sound);
+            }
+        }
+        for id in ImageId::ALL {
+            let decode = |bytes: Cow<[u8]>| image::load_from_memory(&bytes).map(|image| image.to_rgba8()).map_err(|e| e.to_string());
+            if let Some(image) = load_file(dir, id.file_name(), &mut errors, decode) {
+                assets.images.insert(id, image);
+            }
+        }
+        for id in FontId::ALL {
+            let decode = |bytes| {
+                match bytes {
+                    Cow::Borrowed(bytes) => Font::try_from_bytes(bytes),
+                    Cow::Owned(bytes) => Font::try_from_vec(bytes),
+                }
+                .ok_or_else(|| "no es una fuente válida".to_string())
+            };
+            if let Some(font) = load_file(dir, id.file_name(), &mut errors, decode) {
+                assets.fonts.insert(id, font);
+            }
+        }
+        (assets, errors)
+    }
+
+    pub fn sound(&self, id: SoundId) -> Option<Arc<[u8]>> {
+        self.sounds.get(&id).cloned()
+    }
+
+    pub fn image(&self, id: ImageId) -> Option<&RgbaImage> {
+        self.images.get(&id)
+    }
+
+    // Entrega la imagen a quien la va a conservar, sin dejar otra copia aquí
+    pub fn take_image(&mut self, id: ImageId) -> Option<RgbaImage> {
+        self.images.remove(&id)
+    }
+
+    pub fn font(&self, id: FontId) -> Option<&Font<'static>> {
+        self.fonts.get(&id)
+    }
+}
+
+// El archivo en disco tiene prioridad; si falta o está dañado se usa el integrado
+fn load_file<T>(
+    dir: Option<&Path>,
+    file_name: &str,
+    errors: &mut Vec<AssetError>,
+    decode: impl Fn(Cow<'static, [u8]>) -> Result<T, String>,
+) -> Option<T> {
+    let path = dir.map_or_else(|| PathBuf::from(file_name), |dir| dir.join(file_name));
+    let disk = match dir {
+        Some(_) => std::fs::read(&path).map_err(|e| e.to_string()).and_then(|bytes| decode(Cow::Owned(bytes))),
+        None => Err("no hay carpeta de recursos".to_string()),
+    };
+    let reason = match disk {
+        Ok(asset) => return Some(asset),
+        Err(reason) => reason,
+    };
+    let Some(bytes) = embedded(file_name) else {
+        errors.push(AssetError { path, reason });
+        return None;
+    };
+    // Si el archivo existe pero no se pudo leer, se avisa aunque haya copia integrada
+    if dir.is_some() && path.exists() {
+        errors.push(AssetError { path: path.clone(), reason: format!("{}; se usa la copia integrada", reason) });
+    }
+    decode(Cow::Borrowed(bytes)).map_err(|reason| errors.push(AssetError { path, reason })).ok()
+}
+
+fn resolve_from(
+    flag: Option<&Path>,
+    env: Option<OsString>,
+    exe_dir: Option<PathBuf>,
+    cwd: PathBuf,
+) -> Result<PathBuf, DirError> {
+    // Las carpetas indicadas explícitamente deben existir
+    if let Some(dir) = flag {
+        return existing(dir.to_path_buf(), "--assets");
+    }
+    if let Some(dir) = env.filter(|v| !v.is_empty()) {
+        return existing(PathBuf::from(dir), ENV_VAR);
+    }
+    let mut candidates: Vec<PathBuf> = exe_dir.into_iter().chain([cwd]).map(|dir| dir.join(DIR_NAME)).collect();
+    candidates.dedup();
+    match candidates.iter().find(|dir| dir.is_dir()) {
+        Some(dir) => Ok(dir.clone()),
+        None => Err(DirError::NotFound(candidates)),
+    }
+}
+
+fn existing(dir: PathBuf, source: &'static str) -> Result<PathBuf, DirError> {
+    if dir.is_dir() {
+        Ok(dir)
+    } else {
+        Err(DirError::Missing { dir, source })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_from, Assets, DirError, FontId, ImageId, SoundId};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn resolves_flag_then_env_then_exe_dir_then_cwd() {
+        let root = std::env::temp_dir().join(format!("laberinto3d-assets-{}", std::process::id()));
+        let (exe, cwd) = (root.join("bin"), root.join("cwd"));
+        std::fs::create_dir_all(exe.join("assets")).unwrap();
+        std::fs::create_dir_all(cwd.join("assets")).unwrap();
+
+        let flag = cwd.join("assets");
+        assert_eq!(resolve_from(Some(&flag), Some("x".into()), None, PathBuf::new()), Ok(flag));
+        let missing = DirError::Missing { dir: PathBuf::from("/no/existe"), source: "--assets" };
+        assert_eq!(resolve_from(Some(Path::new("/no/existe")), None, None, cwd.clone()), Err(missing));
+        assert_eq!(resolve_from(None, Some(exe.clone().into()), None, cwd.clone()), Ok(exe.clone()));
+        assert_eq!(resolve_from(None, Some("".into()), Some(exe.clone()), cwd.clone()), Ok(exe.join("assets")));
+        assert_eq!(resolve_from(None, None, Some(root.clone()), cwd.clone()), Ok(cwd.join("assets")));
+        assert_eq!(resolve_from(None, None, None, root.clone()), Err(DirError::NotFound(vec![root.join("assets")])));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn loads_every_asset_and_reports_missing_ones() {
+        let (assets, errors) = Assets::load(Some(Path::new("assets")));
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert!(assets.sound(SoundId::Step).is_some());
+        assert!(assets.image(ImageId::Welcome).is_some());
+        assert!(assets.font(FontId::Ui).is_some());
+
+        if !super::EMBEDDED {
+            let (empty, errors) = Assets::load(Some(Path::new("levels")));
+            assert!(empty.image(ImageId::Floor).is_none());
+            assert_eq!(errors.len(), SoundId::ALL.len() + ImageId::ALL.len() + FontId::ALL.len());
+            assert_eq!(errors[0].path, Path::new("levels").join("background_music.mp3"));
+        }
+    }
+
+    #[cfg(feature = "embedded-assets")]
+    #[test]
+    fn disk_files_override_embedded_ones() {
+        let (embedded, errors) = Assets::load(None);
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert!(embedded.sound(SoundId::Music).is_some());
+        assert!(embedded.font(FontId::Ui).is_some());
+
+        let dir = std::env::temp_dir().join(format!("laberinto3d-embedded-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        image::RgbaImage::new(3, 2).save(dir.join("floor.png")).unwrap();
+        std::fs::write(dir.join("ceiling.png"), b"no es una imagen").unwrap();
+        let (assets, errors) = Assets::load(Some(&dir));
+        assert_eq!(assets.image(ImageId::Floor).unwrap().dimensions(), (3, 2));
+        assert_eq!(assets.image(ImageId::Ceiling), embedded.image(ImageId::Ceiling));
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].path.ends_with("ceiling.png"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,9 +1,12 @@
+use proyecto::assets::{Assets, SoundId};
 use rodio::{Decoder, OutputStream, Sink};
-use std::fs::File;
-use std::io::BufReader;
+use std::io::Cursor;
+use std::sync::Arc;
 use rodio::Source; 
 
 pub struct AudioManager {
+    music: Option<Arc<[u8]>>,
+    step: Option<Arc<[u8]>>,
     bg_sink: Option<Sink>,
     bg_stream: Option<OutputStream>,
     fx_sink: Option<Sink>,
@@ -11,8 +14,10 @@ pub struct AudioManager {
 }
 
 impl AudioManager {
-    pub fn new() -> Self {
+    pub fn new(assets: &Assets) -> Self {
         Self {
+            music: assets.sound(SoundId::Music),
+            step: assets.sound(SoundId::Step),
             bg_sink: None,
             bg_stream: None,
             fx_sink: None,
@@ -20,14 +25,13 @@ impl AudioManager {
         }
     }
 
-    pub fn play_running_loop(&mut self, sound_path: &str) {
+    pub fn play_running_loop(&mut self) {
         if self.fx_sink.is_some() {
             return;
         }
         if let Ok((stream, stream_handle)) = OutputStream::try_default() {
-            if let Ok(file) = File::open(sound_path) {
-                let reader = BufReader::new(file);
-                if let Ok(source) = Decoder::new(reader) {
+            if let Some(step) = &self.step {
+                if let Ok(source) = Decoder::new(Cursor::new(step.clone())) {
                     if let Ok(sink) = Sink::try_new(&stream_handle) {
                         sink.set_volume(1.0);
                         sink.append(source.repeat_infinite());
@@ -48,11 +52,10 @@ impl AudioManager {
         self.fx_stream = None;
     }
 
-    pub fn play_background_music(&mut self, music_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn play_background_music(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let music = self.music.clone().ok_or("la música de fondo no está cargada")?;
         let (stream, stream_handle) = OutputStream::try_default()?;
-        let file = File::open(music_path)?;
-        let reader = BufReader::new(file);
-        let source = Decoder::new(reader)?;
+        let source = Decoder::new(Cursor::new(music))?;
         let sink = Sink::try_new(&stream_handle)?;
         sink.set_volume(0.1);
         sink.append(source.repeat_infinite());
@@ -62,7 +65,16 @@ impl AudioManager {
         Ok(())
     }
 
-    pub fn has_audio(&self) -> bool {
-        self.bg_sink.is_some() || self.fx_sink.is_some()
+    // Pausa la música y los efectos en curso sin descartarlos
+    pub fn pause(&self) {
+        for sink in [&self.bg_sink, &self.fx_sink].into_iter().flatten() {
+            sink.pause();
+        }
+    }
+
+    pub fn resume(&self) {
+        for sink in [&self.bg_sink, &self.fx_sink].into_iter().flatten() {
+            sink.play();
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+// Búfer RGBA de la ventana junto con sus dimensiones. La ventana ignora el
+// canal alfa, así que las transparencias se mezclan aquí en RGB y el
+// resultado queda siempre opaco
+pub struct Canvas<'a> {
+    pub pixels: &'a mut [u8],
+    pub width: u32,
+    pub height: u32,
+}
+
+impl<'a> Canvas<'a> {
+    pub fn new(pixels: &'a mut [u8], width: u32, height: u32) -> Self {
+        debug_assert_eq!(pixels.len(), (width * height * 4) as usize);
+        Self { pixels, width, height }
+    }
+
+    // Índice del píxel (x, y) en el búfer, o `None` si queda fuera
+    pub fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+        Some(((y as u32 * self.width + x as u32) * 4) as usize)
+    }
+
+    // Mezcla `color` sobre el píxel con la cobertura dada (0 = nada, 255 = opaco)
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: [u8; 3], coverage: u8) {
+        let Some(idx) = self.index(x, y) else {
+            return;
+        };
+        let a = coverage as u32;
+        for (dst, src) in self.pixels[idx..idx + 3].iter_mut().zip(color) {
+            *dst = ((src as u32 * a + *dst as u32 * (255 - a) + 127) / 255) as u8;
+        }
+        self.pixels[idx + 3] = 0xFF;
+    }
+
+    // Rectángulo semitransparente, recortado a los bordes del búfer
+    pub fn fill_rect_alpha(&mut self, x: i32, y: i32, width: u32, height: u32, color: [u8; 3], alpha: u8) {
+        let (x0, y0) = (x.max(0), y.max(0));
+        let x1 = (x + width as i32).min(self.width as i32);
+        let y1 = (y + height as i32).min(self.height as i32);
+        for py in y0..y1 {
+            for px in x0..x1 {
+                self.blend_pixel(px, py, color, alpha);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Canvas;
+
+    #[test]
+    fn blends_in_rgb_and_stays_opaque() {
+        let mut pixels = [0, 100, 200, 0xFF].repeat(4 * 3);
+        let mut canvas = Canvas::new(&mut pixels, 4, 3);
+        canvas.blend_pixel(0, 0, [255, 255, 255], 0);
+        canvas.blend_pixel(1, 0, [255, 0, 0], 255);
+        canvas.blend_pixel(2, 0, [255, 0, 0], 128);
+        canvas.blend_pixel(-1, 5, [255, 0, 0], 255); // fuera: se ignora
+        assert_eq!(&canvas.pixels[0..12], &[0, 100, 200, 255, 255, 0, 0, 255, 128, 50, 100, 255]);
+
+        // Solo la parte dentro del búfer: columnas 2..4 de las filas 1..3
+        canvas.fill_rect_alpha(2, 1, 10, 10, [0, 0, 0], 255);
+        let dark = canvas.pixels.chunks_exact(4).filter(|px| px[..3] == [0, 0, 0]).count();
+        assert_eq!(dark, 4);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::controls::{KeyBindings, DEFAULT_SENSITIVITY};
+use crate::map::generate::Algorithm;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "uso: proyecto [NIVEL] [--seed N] [--size ANCHOxALTO] [--algorithm backtracker|prim|kruskal]
+               [--keys WSADQE] [--mouse] [--sensitivity S]
+               [--render X,Y,ÁNGULO [--out ARCHIVO.png]] [--assets CARPETA]";
+const DEFAULT_RENDER_OUT: &str = "captura.png";
+
+pub struct Options {
+    pub level: Option<String>,
+    pub seed: Option<u64>,
+    pub size: Option<(usize, usize)>,
+    pub algorithm: Algorithm,
+    pub keys: KeyBindings,
+    pub mouse_look: bool,
+    pub sensitivity: f32,
+    pub render: Option<RenderShot>,
+    pub assets: Option<PathBuf>,
+}
+
+// Captura sin ventana: posición del jugador (ángulo en grados) y archivo de salida
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderShot {
+    pub x: f32,
+    pub y: f32,
+    pub angle: f32,
+    pub out: String,
+}
+
+impl Options {
+    // Recibe los argumentos sin el nombre del programa
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Options {
+            level: None,
+            seed: None,
+            size: None,
+            algorithm: Algorithm::Backtracker,
+            keys: KeyBindings::default(),
+            mouse_look: false,
+            sensitivity: DEFAULT_SENSITIVITY,
+            render: None,
+            assets: None,
+        };
+        let mut out = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |flag: &str| args.next().ok_or_else(|| format!("falta el valor de {}", flag));
+            match arg.as_str() {
+                "--seed" => {
+                    let v = value("--seed")?;
+                    options.seed = Some(v.parse().map_err(|_| format!("semilla inválida '{}'", v))?);
+                }
+                "--size" => options.size = Some(parse_size(&value("--size")?)?),
+                "--algorithm" => options.algorithm = value("--algorithm")?.parse()?,
+                "--keys" => options.keys = KeyBindings::parse(&value("--keys")?)?,
+                "--mouse" => options.mouse_look = true,
+                "--sensitivity" => {
+                    let v = value("--sensitivity")?;
+                    options.sensitivity = match v.parse::<f32>() {
+                        Ok(s) if s > 0.0 => s,
+                        _ => return Err(format!("sensibilidad inválida '{}'", v)),
+                    };
+                }
+                "--render" => {
+                    let (x, y, angle) = parse_pose(&value("--render")?)?;
+                    options.render = Some(RenderShot { x, y, angle, out: String::new() });
+                }
+                "--out" => out = Some(value("--out")?),
+                "--assets" => options.assets = Some(PathBuf::from(value("--assets")?)),
+                flag if flag.starts_with("--") => return Err(format!("opción desconocida '{}'", flag)),
+                _ if options.level.is_none() => options.level = Some(arg),
+                _ => return Err(format!("argumento inesperado '{}'", arg)),
+            }
+        }
+        match &mut options.render {
+            Some(shot) => shot.out = out.unwrap_or_else(|| DEFAULT_RENDER_OUT.to_string()),
+            None if out.is_some() => return Err("--out solo se usa junto con --render".to_string()),
+            None => {}
+        }
+        Ok(options)
+    }
+}
+
+// Tamaño en celdas con el formato ANCHOxALTO (por ejemplo 12x8)
+fn parse_size(text: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("tamaño inválido '{}' (se espera ANCHOxALTO, p. ej. 12x8)", text);
+    let (w, h) = text.split_once(['x', 'X']).ok_or_else(invalid)?;
+    match (w.parse(), h.parse()) {
+        (Ok(w), Ok(h)) if w > 0 && h > 0 => Ok((w, h)),
+        _ => Err(invalid()),
+    }
+}
+
+// Posición con el formato X,Y,ÁNGULO (por ejemplo 1.5,1.5,90)
+fn parse_pose(text: &str) -> Result<(f32, f32, f32), String> {
+    let invalid = || format!("posición inválida '{}' (se espera X,Y,ÁNGULO, p. ej. 1.5,1.5,90)", text);
+    let values: Vec<f32> = text.split(',').map(|part| part.trim().parse()).collect::<Result<_, _>>().map_err(|_| invalid())?;
+    match values[..] {
+        [x, y, angle] if values.iter().all(|v| v.is_finite()) => Ok((x, y, angle)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Options, RenderShot};
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn parses_headless_render() {
+        let options = parse(&["levels/cripta.txt", "--render", "1.5, 2.5,90", "--out", "a.png"]).unwrap();
+        assert_eq!(options.render, Some(RenderShot { x: 1.5, y: 2.5, angle: 90.0, out: "a.png".to_string() }));
+        assert_eq!(parse(&["--render", "1,1,0"]).unwrap().render.unwrap().out, "captura.png");
+        assert!(parse(&["--render", "1,1"]).is_err());
+        assert!(parse(&["--out", "a.png"]).is_err());
+    }
+}
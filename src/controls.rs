@@ -0,0 +1,105 @@
+use winit::event::VirtualKeyCode;
+
+pub const DEFAULT_SENSITIVITY: f32 = 0.003; // radianes por píxel de movimiento del mouse
+const MIN_SENSITIVITY: f32 = 0.0005;
+const MAX_SENSITIVITY: f32 = 0.02;
+
+// Teclas con función fija durante la partida (FPS, pausa, vista con mouse)
+const RESERVED: [VirtualKeyCode; 3] = [VirtualKeyCode::F, VirtualKeyCode::P, VirtualKeyCode::M];
+
+// Teclas de movimiento; se pueden cambiar con `--keys`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBindings {
+    pub forward: VirtualKeyCode,
+    pub back: VirtualKeyCode,
+    pub turn_left: VirtualKeyCode,
+    pub turn_right: VirtualKeyCode,
+    pub strafe_left: VirtualKeyCode,
+    pub strafe_right: VirtualKeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            forward: VirtualKeyCode::W,
+            back: VirtualKeyCode::S,
+            turn_left: VirtualKeyCode::A,
+            turn_right: VirtualKeyCode::D,
+            strafe_left: VirtualKeyCode::Q,
+            strafe_right: VirtualKeyCode::E,
+        }
+    }
+}
+
+impl KeyBindings {
+    // Seis letras o dígitos en el orden: avanzar, retroceder, girar a la
+    // izquierda, girar a la derecha, desplazarse a la izquierda y a la derecha
+    // (por ejemplo "WSADQE" o "IKJLUO")
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let keys: Vec<VirtualKeyCode> = text
+            .chars()
+            .map(|c| key_from_char(c).ok_or_else(|| format!("tecla no soportada '{}'", c)))
+            .collect::<Result<_, _>>()?;
+        let [forward, back, turn_left, turn_right, strafe_left, strafe_right] = keys[..] else {
+            return Err(format!("se esperaban 6 teclas en '{}'", text));
+        };
+        for (i, key) in keys.iter().enumerate() {
+            if RESERVED.contains(key) {
+                return Err(format!("la tecla {:?} ya se usa para otra acción", key));
+            }
+            if keys[..i].contains(key) {
+                return Err(format!("la tecla {:?} está repetida en '{}'", key, text));
+            }
+        }
+        Ok(Self { forward, back, turn_left, turn_right, strafe_left, strafe_right })
+    }
+}
+
+fn key_from_char(c: char) -> Option<VirtualKeyCode> {
+    use VirtualKeyCode::*;
+    const LETTERS: [VirtualKeyCode; 26] = [
+        A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    ];
+    const DIGITS: [VirtualKeyCode; 10] = [Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9];
+    let c = c.to_ascii_uppercase();
+    match c {
+        'A'..='Z' => Some(LETTERS[(c as u8 - b'A') as usize]),
+        '0'..='9' => Some(DIGITS[(c as u8 - b'0') as usize]),
+        _ => None,
+    }
+}
+
+// Vista con el mouse (requiere capturar el cursor mientras se juega)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MouseLook {
+    pub enabled: bool,
+    pub sensitivity: f32,
+}
+
+impl MouseLook {
+    pub fn new(enabled: bool, sensitivity: f32) -> Self {
+        Self { enabled, sensitivity: sensitivity.clamp(MIN_SENSITIVITY, MAX_SENSITIVITY) }
+    }
+
+    pub fn scale_sensitivity(&mut self, factor: f32) {
+        self.sensitivity = (self.sensitivity * factor).clamp(MIN_SENSITIVITY, MAX_SENSITIVITY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyBindings;
+    use winit::event::VirtualKeyCode;
+
+    #[test]
+    fn parses_custom_bindings() {
+        assert_eq!(KeyBindings::parse("wsadqe"), Ok(KeyBindings::default()));
+        let keys = KeyBindings::parse("IKJLUO").unwrap();
+        assert_eq!(keys.forward, VirtualKeyCode::I);
+        assert_eq!(keys.strafe_right, VirtualKeyCode::O);
+        assert!(KeyBindings::parse("WSAD").is_err());
+        assert!(KeyBindings::parse("WSADQQ").is_err());
+        assert!(KeyBindings::parse("WSADQ-").is_err());
+        assert!(KeyBindings::parse("WSADQP").is_err());
+    }
+}
@@ -0,0 +1,83 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FogMode {
+    None,
+    // Crece linealmente entre `start` y `end` (en unidades del mapa)
+    Linear { start: f32, end: f32 },
+    // 1 - e^(-densidad · distancia)
+    Exponential { density: f32 },
+}
+
+// Niebla y atenuación de luz según la distancia, configurables por nivel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fog {
+    pub mode: FogMode,
+    pub color: [u8; 3],
+    pub attenuation: f32, // brillo = 1 / (1 + atenuación · distancia)
+}
+
+impl Fog {
+    pub fn none() -> Self {
+        Self { mode: FogMode::None, color: [0, 0, 0], attenuation: 0.0 }
+    }
+
+    // Proporción de niebla en 0..1 (0 = color original, 1 = solo niebla)
+    pub fn factor(&self, dist: f32) -> f32 {
+        let f = match self.mode {
+            FogMode::None => 0.0,
+            FogMode::Linear { start, end } => {
+                if end <= start {
+                    if dist >= start { 1.0 } else { 0.0 }
+                } else {
+                    (dist - start) / (end - start)
+                }
+            }
+            FogMode::Exponential { density } => 1.0 - (-density * dist).exp(),
+        };
+        f.clamp(0.0, 1.0)
+    }
+
+    pub fn apply(&self, color: [u8; 3], dist: f32) -> [u8; 3] {
+        let light = 1.0 / (1.0 + self.attenuation * dist.max(0.0));
+        let f = self.factor(dist);
+        std::array::from_fn(|i| {
+            let lit = color[i] as f32 * light;
+            (lit + (self.color[i] as f32 - lit) * f) as u8
+        })
+    }
+}
+
+impl Default for Fog {
+    fn default() -> Self {
+        Self {
+            mode: FogMode::Exponential { density: 0.08 },
+            color: [110, 140, 170],
+            attenuation: 0.05,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fog, FogMode};
+
+    #[test]
+    fn linear_fog_ramps_between_start_and_end() {
+        let fog = Fog { mode: FogMode::Linear { start: 2.0, end: 6.0 }, color: [200, 200, 200], attenuation: 0.0 };
+        assert_eq!(fog.factor(1.0), 0.0);
+        assert_eq!(fog.factor(4.0), 0.5);
+        assert_eq!(fog.factor(10.0), 1.0);
+        assert_eq!(fog.apply([0, 100, 200], 1.0), [0, 100, 200]);
+        assert_eq!(fog.apply([0, 100, 200], 10.0), [200, 200, 200]);
+    }
+
+    #[test]
+    fn exponential_fog_and_attenuation_grow_with_distance() {
+        let fog = Fog { mode: FogMode::Exponential { density: 0.5 }, color: [0, 0, 0], attenuation: 1.0 };
+        assert_eq!(fog.factor(0.0), 0.0);
+        assert!(fog.factor(1.0) < fog.factor(2.0));
+        let near = fog.apply([255, 255, 255], 0.5);
+        let far = fog.apply([255, 255, 255], 3.0);
+        assert!(far[0] < near[0]);
+        assert_eq!(Fog::none().apply([10, 20, 30], 100.0), [10, 20, 30]);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::map::Map;
+use crate::player::Player;
+use std::time::Duration;
+
+pub const MOVE_SPEED: f32 = 4.8; // unidades por segundo
+pub const ROT_SPEED: f32 = 3.0;  // radianes por segundo
+pub const TIMESTEP: f32 = 1.0 / 120.0; // paso fijo de la simulación, en segundos
+const GOAL_RADIUS: f32 = 0.5;
+
+// Entrada de un paso de simulación. Los ejes van de -1 a 1 (adelante,
+// derecha y giro horario positivos); `look` es un giro extra en radianes,
+// por ejemplo el del mouse
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InputFrame {
+    pub forward: f32,
+    pub strafe: f32,
+    pub turn: f32,
+    pub look: f32,
+}
+
+// Estadísticas de la partida en curso
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunStats {
+    pub distance: f32, // unidades del mapa recorridas
+    pub turns: u32,    // veces que se comenzó a girar
+    pub finished_time: Option<Duration>,
+}
+
+impl RunStats {
+    // Porcentaje del camino óptimo respecto a la distancia recorrida
+    pub fn efficiency(&self, optimal_steps: usize) -> f32 {
+        if self.distance <= 0.0 {
+            return 0.0;
+        }
+        (optimal_steps as f32 / self.distance * 100.0).min(100.0)
+    }
+
+    // Líneas para la pantalla de éxito
+    pub fn summary(&self, optimal_steps: Option<usize>) -> Vec<String> {
+        let time = self.finished_time.unwrap_or_default().as_secs_f32();
+        let mut lines = vec![
+            format!("Tiempo: {:.2} s", time),
+            format!("Distancia recorrida: {:.1}", self.distance),
+            format!("Giros: {}", self.turns),
+        ];
+        if let Some(optimal) = optimal_steps {
+            lines.push(format!("Camino óptimo: {}", optimal));
+            lines.push(format!("Eficiencia: {:.0}%", self.efficiency(optimal)));
+        }
+        lines
+    }
+}
+
+// Simulación de una partida: movimiento, colisiones y llegada a la meta.
+// No depende de la ventana ni del reloj; con la misma secuencia de entradas
+// y pasos siempre produce el mismo resultado
+pub struct Game {
+    pub map: Map,
+    pub player: Player,
+    pub stats: RunStats,
+    elapsed: f64,     // segundos simulados
+    accumulator: f32, // tiempo de cuadro pendiente de simular
+    turning: bool,
+    finished: bool,
+}
+
+impl Game {
+    pub fn new(map: Map) -> Self {
+        let (x, y) = map.start;
+        Self {
+            map,
+            player: Player::new(x, y, 0.0),
+            stats: RunStats::default(),
+            elapsed: 0.0,
+            accumulator: 0.0,
+            turning: false,
+            finished: false,
+        }
+    }
+
+    // Vuelve a empezar el mismo laberinto
+    pub fn reset(&mut self) {
+        let (x, y) = self.map.start;
+        self.player = Player::new(x, y, 0.0);
+        self.stats = RunStats::default();
+        self.elapsed = 0.0;
+        self.accumulator = 0.0;
+        self.turning = false;
+        self.finished = false;
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs_f64(self.elapsed)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    // Avanza la simulación `dt` segundos con la entrada dada
+    pub fn step(&mut self, input: InputFrame, dt: f32) {
+        if self.finished {
+            return;
+        }
+        let turning = input.turn != 0.0;
+        if turning && !self.turning {
+            self.stats.turns += 1;
+        }
+        self.turning = turning;
+
+        let player = &mut self.player;
+        let (dx, dy) = (player.angle.cos(), player.angle.sin());
+        // Avance sobre la dirección de la vista y desplazamiento lateral sobre
+        // su perpendicular; en diagonal se normaliza para no ir más rápido
+        let (mut mx, mut my) = (dx * input.forward - dy * input.strafe, dy * input.forward + dx * input.strafe);
+        let len = mx.hypot(my);
+        if len > 0.0 {
+            let speed = MOVE_SPEED * dt * len.min(1.0);
+            mx *= speed / len;
+            my *= speed / len;
+            let (old_x, old_y) = (player.x, player.y);
+            player.move_by(mx, my, &self.map);
+            self.stats.distance += (player.x - old_x).hypot(player.y - old_y);
+        }
+        player.angle += input.turn * ROT_SPEED * dt + input.look;
+        self.elapsed += dt as f64;
+
+        // Detección de meta
+        let (goal_x, goal_y) = self.map.end;
+        let dist_to_goal = (player.x - goal_x as f32).hypot(player.y - goal_y as f32);
+        if dist_to_goal < GOAL_RADIUS {
+            self.finished = true;
+            self.stats.finished_time = Some(self.elapsed());
+        }
+    }
+
+    // Consume el tiempo de un cuadro en pasos fijos de `TIMESTEP`; el resto
+    // queda para el cuadro siguiente. Devuelve cuántos pasos se simularon
+    // (`look` se aplica solo en el primero)
+    pub fn advance(&mut self, mut input: InputFrame, frame_dt: f32) -> u32 {
+        self.accumulator += frame_dt;
+        let mut steps = 0;
+        while self.accumulator >= TIMESTEP {
+            self.accumulator -= TIMESTEP;
+            self.step(input, TIMESTEP);
+            input.look = 0.0;
+            steps += 1;
+        }
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Game, InputFrame, MOVE_SPEED, TIMESTEP};
+    use crate::map::Map;
+
+    #[test]
+    fn advance_runs_whole_steps_and_keeps_remainder() {
+        let mut game = Game::new(Map::new());
+        assert_eq!(game.advance(InputFrame::default(), TIMESTEP * 0.6), 0);
+        assert_eq!(game.advance(InputFrame::default(), TIMESTEP * 0.6), 1);
+        assert_eq!(game.advance(InputFrame::default(), TIMESTEP * 3.0), 3);
+        assert!((game.elapsed().as_secs_f32() - TIMESTEP * 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn counts_turns_and_distance() {
+        let map: Map = "#########\n#S.....E#\n#########\n".parse().unwrap();
+        let mut game = Game::new(map);
+        let forward = InputFrame { forward: 1.0, ..Default::default() };
+        let turn = InputFrame { turn: 1.0, ..Default::default() };
+        for input in [forward, turn, turn, InputFrame::default(), turn] {
+            game.step(input, 0.1);
+        }
+        assert_eq!(game.stats.turns, 2);
+        assert!((game.stats.distance - MOVE_SPEED * 0.1).abs() < 1e-4);
+    }
+
+    #[test]
+    fn goal_is_reached_near_the_corner_of_the_exit_cell() {
+        let map: Map = "#########\n#S.....E#\n#########\n".parse().unwrap();
+        let at = |x, y| {
+            let mut game = Game::new(map.clone());
+            (game.player.x, game.player.y) = (x, y);
+            game.step(InputFrame::default(), TIMESTEP);
+            game.is_finished()
+        };
+        // La salida es la celda (7, 1); la meta es el punto (7, 1)
+        assert!(at(7.2, 1.3));
+        assert!(!at(7.5, 1.5));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::canvas::Canvas;
+use image::imageops::{self, FilterType};
+use image::RgbaImage;
+use proyecto::assets::{Assets, ImageId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+// Imagen ya redimensionada para un tamaño de ventana y de búfer concretos
+struct Scaled {
+    surface: (u32, u32),
+    buffer: (u32, u32),
+    width: u32,
+    height: u32,
+    rgba: Vec<u8>,
+}
+
+struct Entry {
+    source: RgbaImage,
+    scaled: Option<Scaled>,
+}
+
+// Imágenes de pantalla completa, que se redimensionan una vez por tamaño de
+// ventana
+pub struct ImageCache {
+    entries: RefCell<HashMap<ImageId, Entry>>,
+    surface: Cell<Option<(u32, u32)>>, // tamaño de la ventana; `None` = el del búfer
+}
+
+const SCREEN_IMAGES: [ImageId; 2] = [ImageId::Welcome, ImageId::Success];
+
+impl ImageCache {
+    // Se queda con las imágenes de pantalla (nadie más las usa); las que no
+    // se cargaron simplemente no se dibujan
+    pub fn new(assets: &mut Assets) -> Self {
+        let entries = SCREEN_IMAGES
+            .into_iter()
+            .filter_map(|id| Some((id, Entry { source: assets.take_image(id)?, scaled: None })))
+            .collect();
+        Self { entries: RefCell::new(entries), surface: Cell::new(None) }
+    }
+
+    // Nuevo tamaño de la ventana, que estira el búfer para ocuparla; las
+    // copias escaladas para el tamaño anterior se descartan
+    pub fn resize(&self, width: u32, height: u32) {
+        let size = (width.max(1), height.max(1));
+        if self.surface.replace(Some(size)) != Some(size) {
+            for entry in self.entries.borrow_mut().values_mut() {
+                entry.scaled = None;
+            }
+        }
+    }
+
+    // Dibuja la imagen centrada, lo más grande posible sin que se vea
+    // deformada en la ventana
+    pub fn draw_centered(&self, canvas: &mut Canvas, id: ImageId) {
+        let mut entries = self.entries.borrow_mut();
+        let Some(entry) = entries.get_mut(&id) else {
+            return;
+        };
+        let buffer = (canvas.width, canvas.height);
+        let surface = self.surface.get().unwrap_or(buffer);
+        if entry.scaled.as_ref().is_none_or(|scaled| scaled.surface != surface || scaled.buffer != buffer) {
+            let (width, height) = buffer_size(entry.source.dimensions(), surface, buffer);
+            let rgba = imageops::resize(&entry.source, width, height, FilterType::Lanczos3).into_raw();
+            entry.scaled = Some(Scaled { surface, buffer, width, height, rgba });
+        }
+        let scaled = entry.scaled.as_ref().expect("recién redimensionada");
+        let x_offset = (canvas.width - scaled.width) / 2;
+        let y_offset = (canvas.height - scaled.height) / 2;
+        let row_len = (scaled.width * 4) as usize;
+        for y in 0..scaled.height {
+            let src = y as usize * row_len;
+            let dst = (((y_offset + y) * canvas.width + x_offset) * 4) as usize;
+            canvas.pixels[dst..dst + row_len].copy_from_slice(&scaled.rgba[src..src + row_len]);
+        }
+    }
+}
+
+// Tamaño de la imagen ajustada a la pantalla conservando su proporción
+fn fit_size((img_w, img_h): (u32, u32), (width, height): (u32, u32)) -> (u32, u32) {
+    let aspect_ratio = img_w as f32 / img_h as f32;
+    if width as f32 / height as f32 > aspect_ratio {
+        (((aspect_ratio * height as f32).round() as u32).clamp(1, width), height)
+    } else {
+        (width, ((width as f32 / aspect_ratio).round() as u32).clamp(1, height))
+    }
+}
+
+// Tamaño dentro del búfer para que, una vez estirado a la ventana, la imagen
+// se vea con su proporción original
+fn buffer_size(image: (u32, u32), surface: (u32, u32), buffer: (u32, u32)) -> (u32, u32) {
+    let (width, height) = fit_size(image, surface);
+    let to_buffer = |v: u32, from: u32, to: u32| ((v as f32 * to as f32 / from as f32).round() as u32).clamp(1, to);
+    (to_buffer(width, surface.0, buffer.0), to_buffer(height, surface.1, buffer.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{buffer_size, fit_size, ImageCache};
+    use crate::canvas::Canvas;
+    use proyecto::assets::{Assets, ImageId};
+    use std::path::Path;
+
+    #[test]
+    fn fit_size_keeps_aspect_ratio() {
+        assert_eq!(fit_size((1024, 1024), (640, 480)), (480, 480));
+        assert_eq!(fit_size((1600, 400), (640, 480)), (640, 160));
+        assert_eq!(fit_size((640, 480), (640, 480)), (640, 480));
+    }
+
+    #[test]
+    fn buffer_size_compensates_window_stretch() {
+        // Sin estirar es el ajuste normal
+        assert_eq!(buffer_size((1024, 1024), (640, 480), (640, 480)), (480, 480));
+        // Una ventana el doble de ancha estira el búfer solo en horizontal
+        assert_eq!(buffer_size((1024, 1024), (1280, 480), (640, 480)), (240, 480));
+    }
+
+    #[test]
+    fn scales_once_per_window_size() {
+        let (mut assets, _) = Assets::load(Some(Path::new("assets")));
+        let cache = ImageCache::new(&mut assets);
+        assert!(assets.image(ImageId::Welcome).is_none());
+        let mut small = vec![0; 64 * 48 * 4];
+        cache.draw_centered(&mut Canvas::new(&mut small, 64, 48), ImageId::Welcome);
+        let scaled_size = |cache: &ImageCache| {
+            let entries = cache.entries.borrow();
+            entries[&ImageId::Welcome].scaled.as_ref().map(|s| s.buffer)
+        };
+        assert_eq!(scaled_size(&cache), Some((64, 48)));
+        assert!(small.chunks_exact(4).any(|px| px[..3] != [0, 0, 0]));
+
+        let mut large = vec![0; 128 * 96 * 4];
+        cache.draw_centered(&mut Canvas::new(&mut large, 128, 96), ImageId::Welcome);
+        assert_eq!(scaled_size(&cache), Some((128, 96)));
+        // Con el mismo tamaño se reutiliza la copia ya escalada
+        let scaled_ptr = |cache: &ImageCache| cache.entries.borrow()[&ImageId::Welcome].scaled.as_ref().map(|s| s.rgba.as_ptr());
+        let before = scaled_ptr(&cache);
+        cache.draw_centered(&mut Canvas::new(&mut large, 128, 96), ImageId::Welcome);
+        assert_eq!(scaled_ptr(&cache), before);
+
+        // Cambiar el tamaño de la ventana descarta la copia; el mismo tamaño no
+        cache.resize(256, 96);
+        assert_eq!(scaled_size(&cache), None);
+        cache.draw_centered(&mut Canvas::new(&mut large, 128, 96), ImageId::Welcome);
+        let before = scaled_ptr(&cache);
+        cache.resize(256, 96);
+        assert_eq!(scaled_ptr(&cache), before);
+
+        // Una imagen que no se cargó no dibuja nada
+        let empty = ImageCache::new(&mut Assets::default());
+        let mut blank = vec![0; 64 * 48 * 4];
+        empty.draw_centered(&mut Canvas::new(&mut blank, 64, 48), ImageId::Welcome);
+        assert!(blank.iter().all(|&b| b == 0));
+    }
+}
@@ -0,0 +1,187 @@
+use crate::map::Map;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const MAX_ENTRIES: usize = 10;
+pub const MAX_NAME_LEN: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    pub time_ms: u64,
+}
+
+// Mejores tiempos por laberinto, guardados en un archivo de texto con una
+// marca por línea: `<clave>\t<milisegundos>\t<nombre>`
+pub struct Leaderboard {
+    path: Option<PathBuf>,
+    entries: HashMap<String, Vec<Entry>>,
+}
+
+impl Leaderboard {
+    // Usa el directorio de datos del usuario; si no existe el archivo, empieza vacío
+    pub fn load() -> Self {
+        match data_dir() {
+            Some(dir) => Self::load_from(dir.join("laberinto3d").join("leaderboard.tsv")),
+            None => Self { path: None, entries: HashMap::new() },
+        }
+    }
+
+    pub fn load_from<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let mut entries: HashMap<String, Vec<Entry>> = HashMap::new();
+        if let Ok(text) = std::fs::read_to_string(&path) {
+            for line in text.lines() {
+                let mut parts = line.splitn(3, '\t');
+                if let (Some(key), Some(time), Some(name)) = (parts.next(), parts.next(), parts.next()) {
+                    if let Ok(time_ms) = time.parse() {
+                        entries.entry(key.to_string()).or_default().push(Entry { name: name.to_string(), time_ms });
+                    }
+                }
+            }
+        }
+        for list in entries.values_mut() {
+            list.sort_by_key(|e| e.time_ms);
+            list.truncate(MAX_ENTRIES);
+        }
+        Self { path: Some(path), entries }
+    }
+
+    pub fn top(&self, key: &str) -> &[Entry] {
+        self.entries.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    // Indica si el tiempo entra en los mejores de ese laberinto
+    pub fn qualifies(&self, key: &str, time: Duration) -> bool {
+        let top = self.top(key);
+        top.len() < MAX_ENTRIES || top.last().is_some_and(|last| (time.as_millis() as u64) < last.time_ms)
+    }
+
+    // Agrega una marca y devuelve su posición (desde 1) si quedó en la tabla
+    pub fn record(&mut self, key: &str, name: &str, time: Duration) -> Option<usize> {
+        let name: String = name.chars().filter(|c| !c.is_control()).take(MAX_NAME_LEN).collect();
+        let name = if name.trim().is_empty() { "Anónimo".to_string() } else { name.trim().to_string() };
+        let time_ms = time.as_millis() as u64;
+        let list = self.entries.entry(key.to_string()).or_default();
+        // Los empates quedan detrás de las marcas anteriores
+        let rank = list.iter().take_while(|e| e.time_ms <= time_ms).count();
+        if rank >= MAX_ENTRIES {
+            return None;
+        }
+        list.insert(rank, Entry { name, time_ms });
+        list.truncate(MAX_ENTRIES);
+        Some(rank + 1)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "no hay directorio de datos"));
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut keys: Vec<_> = self.entries.keys().collect();
+        keys.sort();
+        let mut text = String::new();
+        for key in keys {
+            for entry in &self.entries[key] {
+                text.push_str(&format!("{}\t{}\t{}\n", key, entry.time_ms, entry.name));
+            }
+        }
+        std::fs::write(path, text)
+    }
+}
+
+// Identificador estable de un laberinto: hash FNV-1a de la cuadrícula,
+// la entrada y la salida
+pub fn map_key(map: &Map) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |bytes: &[u8]| {
+        for &b in bytes {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    };
+    for row in &map.grid {
+        feed(&(row.len() as u32).to_le_bytes());
+        feed(row);
+    }
+    feed(&map.start.0.to_le_bytes());
+    feed(&map.start.1.to_le_bytes());
+    feed(&(map.end.0 as u32).to_le_bytes());
+    feed(&(map.end.1 as u32).to_le_bytes());
+    format!("{:016x}", hash)
+}
+
+pub fn format_time(time_ms: u64) -> String {
+    format!("{}:{:02}.{:02}", time_ms / 60_000, time_ms / 1000 % 60, time_ms % 1000 / 10)
+}
+
+// Directorio de datos del usuario según la plataforma
+fn data_dir() -> Option<PathBuf> {
+    let env = |name| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    if cfg!(windows) {
+        env("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env("XDG_DATA_HOME").or_else(|| env("HOME").map(|home| home.join(".local").join("share")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_time, map_key, Leaderboard, MAX_ENTRIES};
+    use crate::map::Map;
+    use std::time::Duration;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("laberinto3d-test-{}", std::process::id()));
+        dir.join(name)
+    }
+
+    #[test]
+    fn keeps_best_times_sorted_and_persists_them() {
+        let path = temp_path("sorted.tsv");
+        let _ = std::fs::remove_file(&path);
+        let mut board = Leaderboard::load_from(&path);
+        assert_eq!(board.record("a", "Ana", Duration::from_millis(30_000)), Some(1));
+        assert_eq!(board.record("a", "Beto", Duration::from_millis(20_000)), Some(1));
+        assert_eq!(board.record("a", "Caro\tX", Duration::from_millis(25_000)), Some(2));
+        assert_eq!(board.record("b", "", Duration::from_millis(1_000)), Some(1));
+        board.save().unwrap();
+
+        let loaded = Leaderboard::load_from(&path);
+        let names: Vec<_> = loaded.top("a").iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Beto", "CaroX", "Ana"]);
+        assert_eq!(loaded.top("b")[0].name, "Anónimo");
+        assert!(loaded.top("c").is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn only_top_ten_qualify() {
+        let mut board = Leaderboard::load_from(temp_path("unused.tsv"));
+        for i in 0..MAX_ENTRIES as u64 {
+            assert!(board.qualifies("k", Duration::from_secs(10 + i)));
+            board.record("k", "x", Duration::from_secs(10 + i));
+        }
+        assert!(!board.qualifies("k", Duration::from_secs(60)));
+        assert_eq!(board.record("k", "lento", Duration::from_secs(60)), None);
+        assert!(board.qualifies("k", Duration::from_secs(5)));
+        assert_eq!(board.record("k", "rápido", Duration::from_secs(5)), Some(1));
+        assert_eq!(board.top("k").len(), MAX_ENTRIES);
+    }
+
+    #[test]
+    fn map_key_depends_on_layout() {
+        let a = Map::new();
+        let mut b = Map::new();
+        assert_eq!(map_key(&a), map_key(&b));
+        b.grid[1][2] = 1;
+        assert_ne!(map_key(&a), map_key(&b));
+        assert_eq!(format_time(83_456), "1:23.45");
+    }
+}
@@ -0,0 +1,156 @@
+use crate::cli::Options;
+use crate::map::generate::{generate, Algorithm};
+use crate::map::Map;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_MAZE_SIZE: (usize, usize) = (10, 8);
+const LEVELS_DIR: &str = "levels";
+
+// Origen del laberinto actual, para poder repetirlo o pasar al siguiente
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelSource {
+    Builtin,
+    File(PathBuf),
+    Generated { width: usize, height: usize, algorithm: Algorithm, seed: u64 },
+}
+
+impl LevelSource {
+    // Archivo de nivel, laberinto generado con `--seed`/`--size`, o el integrado
+    pub fn from_options(options: &Options) -> Self {
+        if let Some(path) = &options.level {
+            return LevelSource::File(PathBuf::from(path));
+        }
+        if options.seed.is_none() && options.size.is_none() {
+            return LevelSource::Builtin;
+        }
+        let (width, height) = options.size.unwrap_or(DEFAULT_MAZE_SIZE);
+        LevelSource::Generated {
+            width,
+            height,
+            algorithm: options.algorithm,
+            seed: options.seed.unwrap_or_else(random_seed),
+        }
+    }
+
+    // Laberinto nuevo con semilla aleatoria y el mismo tamaño que el actual
+    pub fn random(&self) -> Self {
+        let (width, height, algorithm) = match *self {
+            LevelSource::Generated { width, height, algorithm, .. } => (width, height, algorithm),
+            _ => (DEFAULT_MAZE_SIZE.0, DEFAULT_MAZE_SIZE.1, Algorithm::Backtracker),
+        };
+        LevelSource::Generated { width, height, algorithm, seed: random_seed() }
+    }
+
+    // Siguiente nivel: el próximo archivo de la misma carpeta (en orden
+    // alfabético) o la siguiente semilla para los laberintos generados
+    pub fn next(&self) -> Self {
+        match self {
+            LevelSource::Builtin => level_files(Path::new(LEVELS_DIR))
+                .into_iter()
+                .next()
+                .map(LevelSource::File)
+                .unwrap_or_else(|| self.random()),
+            LevelSource::File(path) => {
+                let dir = path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+                let files = level_files(dir);
+                let current = files.iter().position(|f| same_file(f, path));
+                match current {
+                    Some(i) => LevelSource::File(files[(i + 1) % files.len()].clone()),
+                    None => files.into_iter().next().map(LevelSource::File).unwrap_or(LevelSource::Builtin),
+                }
+            }
+            &LevelSource::Generated { width, height, algorithm, seed } => LevelSource::Generated {
+                width,
+                height,
+                algorithm,
+                seed: seed.wrapping_add(1),
+            },
+        }
+    }
+
+    // Construye el mapa; los niveles de archivo además deben ser jugables
+    pub fn load(&self) -> Result<Map, String> {
+        match self {
+            LevelSource::Builtin => Ok(Map::new()),
+            LevelSource::File(path) => {
+                let map = Map::load(path)
+                    .map_err(|e| format!("No se pudo cargar el nivel '{}': {}", path.display(), e))?;
+                let issues = map.validate();
+                if issues.is_empty() {
+                    return Ok(map);
+                }
+                let mut message = format!("El nivel '{}' no es jugable:", path.display());
+                for issue in issues {
+                    message.push_str(&format!("\n  - {}", issue));
+                }
+                Err(message)
+            }
+            &LevelSource::Generated { width, height, algorithm, seed } => {
+                println!("Laberinto {}x{} ({}), semilla {}", width, height, algorithm, seed);
+                Ok(generate(width, height, algorithm, seed))
+            }
+        }
+    }
+}
+
+fn random_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+fn level_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "txt"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LevelSource;
+    use crate::map::generate::Algorithm;
+
+    #[test]
+    fn next_walks_level_files_in_order_and_wraps() {
+        let dir = std::env::temp_dir().join(format!("laberinto3d-levels-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["b.txt", "a.txt", "c.txt", "notas.md"] {
+            std::fs::write(dir.join(name), "#####\n#S.E#\n#####\n").unwrap();
+        }
+        let level = |name: &str| LevelSource::File(dir.join(name));
+
+        assert_eq!(level("a.txt").next(), level("b.txt"));
+        assert_eq!(level("b.txt").next(), level("c.txt"));
+        assert_eq!(level("c.txt").next(), level("a.txt"));
+        // Un archivo fuera de la lista empieza por el primero
+        assert_eq!(level("notas.md").next(), level("a.txt"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn next_generated_level_uses_following_seed() {
+        let level = LevelSource::Generated { width: 4, height: 3, algorithm: Algorithm::Prim, seed: 9 };
+        let next = level.next();
+        assert_eq!(next, LevelSource::Generated { width: 4, height: 3, algorithm: Algorithm::Prim, seed: 10 });
+        assert_ne!(level.load().unwrap().grid, next.load().unwrap().grid);
+        match level.random() {
+            LevelSource::Generated { width: 4, height: 3, algorithm: Algorithm::Prim, .. } => {}
+            other => panic!("se esperaba un laberinto generado, no {:?}", other),
+        }
+    }
+}
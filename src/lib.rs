@@ -0,0 +1,9 @@
+// Núcleo del juego y del dibujo, independiente de la ventana y del audio
+pub mod assets;
+pub mod fog;
+pub mod game;
+pub mod map;
+pub mod player;
+pub mod raycaster;
+pub mod render;
+pub mod textures;
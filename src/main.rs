@@ -1,31 +1,36 @@
-mod player;
-mod map;
-mod raycaster;
-mod render;
+mod app;
 mod audio;
+mod canvas;
+mod cli;
+mod controls;
+mod image_cache;
+mod leaderboard;
+mod level;
 mod screens;
+mod screenshot;
+mod text;
+
+use proyecto::assets::{self, Assets, DirError, FontId};
+use proyecto::{game, map, render, textures};
 
 use pixels::{Pixels, SurfaceTexture};
-use winit::event::{Event, WindowEvent, ElementState, VirtualKeyCode};
+use winit::event::{DeviceEvent, Event, WindowEvent, ElementState};
 use winit::event_loop::{ControlFlow, EventLoop};
-use winit::window::WindowBuilder;
-use render::render_3d_basic;
+use winit::window::{CursorGrabMode, Window, WindowBuilder};
 use std::time::{Instant, Duration};
 
 const WIDTH: u32 = 640;
 const HEIGHT: u32 = 480;
-const FOV: f32 = std::f32::consts::FRAC_PI_3;
-const NUM_RAYS: u32 = WIDTH;
-const MAX_DEPTH: f32 = 16.0;
-const MOVE_SPEED: f32 = 0.08;
-const ROT_SPEED: f32 = 0.05;
+const MAX_FRAME_DT: f32 = 0.1; // evita saltos grandes tras una pausa larga del sistema
 const TARGET_FPS: u64 = 60;
 const FRAME_TIME: Duration = Duration::from_nanos(1_000_000_000 / TARGET_FPS);
 
 struct GameState {
-    player: player::Player,
-    map: map::Map,
+    game: game::Game,
     audio_manager: audio::AudioManager,
+    textures: textures::TextureAtlas,
+    text: text::TextRenderer,
+    images: image_cache::ImageCache,
 }
 
 struct FpsCounter {
@@ -68,6 +73,27 @@ impl FpsCounter {
 }
 
 fn main() {
+    let options = match cli::Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let mut assets = load_assets(&options);
+    // Modo sin ventana: dibuja una sola vista en un PNG y termina
+    if let Some(shot) = &options.render {
+        let result = level::LevelSource::from_options(&options)
+            .load()
+            .and_then(|map| render_headless(map, shot, &assets));
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let event_loop = EventLoop::new();
     let window = WindowBuilder::new()
         .with_title("Laberinto 3D - Humberto de la Cruz")
@@ -76,148 +102,86 @@ fn main() {
         .unwrap();
 
     let mut pixels = Pixels::new(WIDTH, HEIGHT, SurfaceTexture::new(WIDTH, HEIGHT, &window)).unwrap();
-    let mut show_welcome = true;
-    let mut show_success = false;
-    let mut show_fps = true;
-    let mut keys = [false; 4];
-    let map = map::Map::new();
+    let mut level = level::LevelSource::from_options(&options);
+    let map = level.load().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        eprintln!("Se usará el laberinto integrado");
+        level = level::LevelSource::Builtin;
+        map::Map::new()
+    });
     let mut state = GameState {
-        player: player::Player::new(map.start.0, map.start.1, 0.0),
-        map,
-        audio_manager: audio::AudioManager::new(),
+        game: game::Game::new(map),
+        audio_manager: audio::AudioManager::new(&assets),
+        textures: textures::TextureAtlas::from_assets(&assets),
+        text: text::TextRenderer::new(assets.font(FontId::Ui).cloned()),
+        images: image_cache::ImageCache::new(&mut assets),
     };
+    let size = window.inner_size();
+    state.images.resize(size.width, size.height);
     
-    if let Err(e) = state.audio_manager.play_background_music("assets/background_music.mp3") {
+    if let Err(e) = state.audio_manager.play_background_music() {
         eprintln!("No se pudo reproducir música de fondo: {}", e);
-        eprintln!("Asegúrate de tener un archivo 'background_music.mp3' en la carpeta 'assets/'");
     }
     
+    let mouse = controls::MouseLook::new(options.mouse_look, options.sensitivity);
+    let mut app = app::App::new(state, level, options.keys, mouse);
+    let mut cursor_grabbed = false;
     let mut fps_counter = FpsCounter::new();
-    let mut start_time = Instant::now();
-    let mut elapsed_time = 0.0;
-    let mut finished_time: Option<f32> = None;
+    let mut last_update = Instant::now();
 
     event_loop.run(move |event, _, control_flow| {
         match event {
             Event::WindowEvent { event, .. } => match event {
                 WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
                 WindowEvent::KeyboardInput { input, .. } => {
-                    if let Some(VirtualKeyCode::Escape) = input.virtual_keycode {
-                        if input.state == ElementState::Pressed {
-                            *control_flow = ControlFlow::Exit;
-                        }
-                    }
-                    if show_welcome {
-                        if let Some(VirtualKeyCode::Space) = input.virtual_keycode {
-                            if input.state == ElementState::Pressed {
-                                show_welcome = false;
-                            }
-                        }
-                    } else if show_success {
-                        if let Some(VirtualKeyCode::Escape) = input.virtual_keycode {
-
-                            if input.state == ElementState::Pressed {
-                                // Terminar el juego
-                                *control_flow = ControlFlow::Exit;
-                            }
-                        }
-                    } else {
-                        // Movimiento: W, S, A, D
+                    if let Some(key) = input.virtual_keycode {
                         let pressed = input.state == ElementState::Pressed;
-                        match input.virtual_keycode {
-                            Some(VirtualKeyCode::W) => {
-                                if input.state == ElementState::Pressed && !keys[0] {
-                                    state.audio_manager.play_running_loop("assets/step.mp3");
-                                }
-                                if input.state == ElementState::Released && keys[0] {
-                                    state.audio_manager.stop_running_loop();
-                                }
-                                keys[0] = pressed;
-                            },
-                            Some(VirtualKeyCode::S) => {
-                                if input.state == ElementState::Pressed && !keys[1] {
-                                    state.audio_manager.play_running_loop("assets/step.mp3");
-                                }
-                                if input.state == ElementState::Released && keys[1] {
-                                    state.audio_manager.stop_running_loop();
-                                }
-                                keys[1] = pressed;
-                            },
-                            Some(VirtualKeyCode::A) => keys[2] = pressed,
-                            Some(VirtualKeyCode::D) => keys[3] = pressed,
-                            Some(VirtualKeyCode::F) => {
-                                if input.state == ElementState::Pressed {
-                                    show_fps = !show_fps;
-                                }
-                            }
-                            _ => {}
+                        if app.handle_key(key, pressed) == app::Command::Quit {
+                            *control_flow = ControlFlow::Exit;
                         }
                     }
                 }
+                WindowEvent::ReceivedCharacter(c) => app.handle_char(c),
+                // Las imágenes de pantalla se vuelven a escalar para el nuevo tamaño
+                WindowEvent::Resized(size) => app.state.images.resize(size.width, size.height),
                 _ => {}
             },
+            Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta }, .. } => {
+                app.handle_mouse_motion(delta.0);
+            }
             Event::RedrawRequested(_) => {
                 let frame_start = Instant::now();
-                let frame = pixels.frame_mut();
 
-                if !show_success && !show_welcome {
-                elapsed_time = start_time.elapsed().as_secs_f32();
-                }
-                
-                let frame = pixels.frame_mut();
-                if show_welcome {
-                    screens::show_welcome_screen(frame, WIDTH, HEIGHT);
-                } else if show_success {
-                    screens::show_success_screen(frame, WIDTH, HEIGHT);
-                } else {
-                    // Actualizar movimiento
-                    let (dx, dy) = (state.player.angle.cos(), state.player.angle.sin());
-                    if keys[0] { // W
-                        let nx = state.player.x + dx * MOVE_SPEED;
-                        let ny = state.player.y + dy * MOVE_SPEED;
-                        if !state.map.is_wall(nx as usize, state.player.y as usize) {
-                            state.player.x = nx;
-                        }
-                        if !state.map.is_wall(state.player.x as usize, ny as usize) {
-                            state.player.y = ny;
-                        }
-                    }
-                    if keys[1] { // S
-                        let nx = state.player.x - dx * MOVE_SPEED;
-                        let ny = state.player.y - dy * MOVE_SPEED;
-                        if !state.map.is_wall(nx as usize, state.player.y as usize) {
-                            state.player.x = nx;
-                        }
-                        if !state.map.is_wall(state.player.x as usize, ny as usize) {
-                            state.player.y = ny;
-                        }
-                    }
-                    if keys[2] { // A
-                        state.player.angle -= ROT_SPEED;
-                    }
-                    if keys[3] { // D
-                        state.player.angle += ROT_SPEED;
-                    }
-                    // Detección de meta
-                    let (goal_x, goal_y) = state.map.end;
-                    let dist_to_goal = ((state.player.x - goal_x as f32).powi(2) + (state.player.y - goal_y as f32).powi(2)).sqrt();
-                    if dist_to_goal < 0.5 {
-                        show_success = true;
-                    }
-                    // Renderizado 3D
-                    render_3d_basic(frame, &state);
+                if app.wants_cursor_grab() != cursor_grabbed {
+                    cursor_grabbed = app.wants_cursor_grab();
+                    set_cursor_grab(&window, cursor_grabbed);
                 }
 
+                let dt = frame_start.duration_since(last_update).as_secs_f32().min(MAX_FRAME_DT);
+                last_update = frame_start;
+                app.update(dt);
+                let mut canvas = canvas::Canvas::new(pixels.frame_mut(), WIDTH, HEIGHT);
+                app.render(&mut canvas);
+
+                // Mostrar FPS y tiempo en pantalla
                 fps_counter.update(frame_start.elapsed());
-                if show_fps {
-                    render_fps_overlay(frame, WIDTH, HEIGHT, fps_counter.get_fps(), fps_counter.get_frame_time());
-                    render_timer_overlay(frame, WIDTH, HEIGHT, elapsed_time);
+                if app.show_fps {
+                    let text = &app.state.text;
+                    render_fps_overlay(&mut canvas, text, fps_counter.get_fps(), fps_counter.get_frame_time());
+                    render_timer_overlay(&mut canvas, text, app.state.game.elapsed().as_secs_f32());
                 }
-                
-                // Mostrar FPS en pantalla
-                fps_counter.update(frame_start.elapsed());
-                if show_fps {
-                    render_fps_overlay(frame, WIDTH, HEIGHT, fps_counter.get_fps(), fps_counter.get_frame_time());
+                // La captura incluye todo lo dibujado salvo el propio aviso
+                if app.take_screenshot_request() {
+                    match screenshot::save(canvas.pixels, WIDTH, HEIGHT) {
+                        Ok(path) => app.notify(format!("Captura guardada en {}", path.display())),
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            app.notify("No se pudo guardar la captura".to_string());
+                        }
+                    }
+                }
+                if let Some(notice) = app.notice() {
+                    render_notice_overlay(&mut canvas, &app.state.text, notice);
                 }
                 
                 pixels.render().unwrap();
@@ -240,124 +204,112 @@ fn main() {
     });
 }
 
-fn render_fps_overlay(frame: &mut [u8], width: u32, height: u32, fps: f32, frame_time: f32) {
-    // Dibujar un rectángulo semi-transparente para el FPS
-    let overlay_width = 180;
-    let overlay_height = 40;
-    let x = width - overlay_width - 10;
-    let y = 10;
-    
-    for dy in 0..overlay_height {
-        for dx in 0..overlay_width {
-            let px = x + dx;
-            let py = y + dy;
-            if px < width && py < height {
-                let idx = ((py * width + px) * 4) as usize;
-                frame[idx] = 0;     // R
-                frame[idx + 1] = 0; // G
-                frame[idx + 2] = 0; // B
-                frame[idx + 3] = 128; // A 
-            }
+// Busca la carpeta de recursos y carga todo. Una carpeta indicada con
+// `--assets` o con la variable de entorno debe existir; si la búsqueda
+// automática no encuentra ninguna, el juego sigue sin recursos
+fn load_assets(options: &cli::Options) -> Assets {
+    let dir = match Assets::resolve_dir(options.assets.as_deref()) {
+        Ok(dir) => Some(dir),
+        Err(e @ DirError::Missing { .. }) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
         }
-    }
-    
-    // Cargar la fuente para el texto FPS
-    if let Ok(font_data) = std::fs::read("assets/DejaVuSans.ttf") {
-        if let Some(font) = rusttype::Font::try_from_vec(font_data) {
-            let fps_text = format!("FPS: {:.1}", fps);
-            let frame_text = format!("Frame: {:.1}ms", frame_time);
-            
-            let scale = rusttype::Scale::uniform(14.0);
-            let v_metrics = font.v_metrics(scale);
-            
-            let start_x = x as f32 + 5.0;
-            let start_y = y as f32 + 15.0;
-            
-            // Dibujar FPS
-            for glyph in font.layout(&fps_text, scale, rusttype::point(start_x, start_y + v_metrics.ascent)) {
-                if let Some(bounding_box) = glyph.pixel_bounding_box() {
-                    glyph.draw(|gx, gy, v| {
-                        let px = gx as i32 + bounding_box.min.x;
-                        let py = gy as i32 + bounding_box.min.y;
-                        
-                        if px >= 0 && px < width as i32 && py >= 0 && py < height as i32 {
-                            let idx = ((py as u32 * width + px as u32) * 4) as usize;
-                            let alpha = (v * 255.0) as u8;
-                            frame[idx] = 255;     // R (blanco)
-                            frame[idx + 1] = 255; // G
-                            frame[idx + 2] = 255; // B
-                            frame[idx + 3] = alpha;
-                        }
-                    });
-                }
-            }
-            
-            // Dibujar Frame time
-            let start_y2 = y as f32 + 30.0;
-            for glyph in font.layout(&frame_text, scale, rusttype::point(start_x, start_y2 + v_metrics.ascent)) {
-                if let Some(bounding_box) = glyph.pixel_bounding_box() {
-                    glyph.draw(|gx, gy, v| {
-                        let px = gx as i32 + bounding_box.min.x;
-                        let py = gy as i32 + bounding_box.min.y;
-                        
-                        if px >= 0 && px < width as i32 && py >= 0 && py < height as i32 {
-                            let idx = ((py as u32 * width + px as u32) * 4) as usize;
-                            let alpha = (v * 255.0) as u8;
-                            frame[idx] = 255;     // R (blanco)
-                            frame[idx + 1] = 255; // G
-                            frame[idx + 2] = 255; // B
-                            frame[idx + 3] = alpha;
-                        }
-                    });
-                }
-            }
+        Err(e) if assets::EMBEDDED => {
+            eprintln!("{}; se usarán los recursos integrados", e);
+            None
+        }
+        Err(e) => {
+            eprintln!("{}; el juego seguirá sin imágenes, sonidos ni fuente", e);
+            return Assets::default();
+        }
+    };
+    let (assets, errors) = Assets::load(dir.as_deref());
+    if !errors.is_empty() {
+        eprintln!("No se pudieron cargar algunos recursos:");
+        for error in &errors {
+            eprintln!("  {}", error);
         }
     }
+    assets
 }
 
-fn render_timer_overlay(frame: &mut [u8], width: u32, height: u32, elapsed_time: f32) {
-    let overlay_width = 180;
-    let overlay_height = 20;
-    let x = width - overlay_width - 10;
-    let y = 55; 
+// Dibuja la vista desde la posición pedida y la guarda como PNG
+fn render_headless(map: map::Map, shot: &cli::RenderShot, assets: &Assets) -> Result<(), String> {
+    if shot.x < 0.0 || shot.y < 0.0 || map.is_wall(shot.x as usize, shot.y as usize) {
+        return Err(format!("La posición ({}, {}) está dentro de una pared o fuera del laberinto", shot.x, shot.y));
+    }
+    let mut game = game::Game::new(map);
+    game.player.x = shot.x;
+    game.player.y = shot.y;
+    game.player.angle = shot.angle.to_radians();
+    let image = render::render_to_image(WIDTH, HEIGHT, &game, &textures::TextureAtlas::from_assets(assets));
+    image
+        .save(&shot.out)
+        .map_err(|e| format!("No se pudo guardar la captura '{}': {}", shot.out, e))?;
+    println!("Captura guardada en {}", shot.out);
+    Ok(())
+}
 
-    for dy in 0..overlay_height {
-        for dx in 0..overlay_width {
-            let px = x + dx;
-            let py = y + dy;
-            if px < width && py < height {
-                let idx = ((py * width + px) * 4) as usize;
-                frame[idx] = 0;
-                frame[idx + 1] = 0;
-                frame[idx + 2] = 0;
-                frame[idx + 3] = 128;
-            }
-        }
+// Captura u oculta el cursor para la vista con mouse; no todas las
+// plataformas soportan el mismo modo, así que se prueban ambos
+fn set_cursor_grab(window: &Window, grab: bool) {
+    let result = if grab {
+        window
+            .set_cursor_grab(CursorGrabMode::Locked)
+            .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined))
+    } else {
+        window.set_cursor_grab(CursorGrabMode::None)
+    };
+    if let Err(e) = result {
+        eprintln!("No se pudo capturar el cursor: {}", e);
     }
-    if let Ok(font_data) = std::fs::read("assets/DejaVuSans.ttf") {
-        if let Some(font) = rusttype::Font::try_from_vec(font_data) {
-            let timer_text = format!("Tiempo: {:.2} s", elapsed_time);
-            let scale = rusttype::Scale::uniform(14.0);
-            let v_metrics = font.v_metrics(scale);
-            let start_x = x as f32 + 5.0;
-            let start_y = y as f32 + 15.0;
+    window.set_cursor_visible(!grab);
+}
 
-            for glyph in font.layout(&timer_text, scale, rusttype::point(start_x, start_y + v_metrics.ascent)) {
-                if let Some(bounding_box) = glyph.pixel_bounding_box() {
-                    glyph.draw(|gx, gy, v| {
-                        let px = gx as i32 + bounding_box.min.x;
-                        let py = gy as i32 + bounding_box.min.y;
-                        if px >= 0 && px < width as i32 && py >= 0 && py < height as i32 {
-                            let idx = ((py as u32 * width + px as u32) * 4) as usize;
-                            let alpha = (v * 255.0) as u8;
-                            frame[idx] = 255;
-                            frame[idx + 1] = 255;
-                            frame[idx + 2] = 0; 
-                            frame[idx + 3] = alpha;
-                        }
-                    });
-                }
-            }
-        }
+fn render_fps_overlay(canvas: &mut canvas::Canvas, text: &text::TextRenderer, fps: f32, frame_time: f32) {
+    let x = canvas.width - 180 - 10;
+    let y: u32 = 10;
+    // Recuadro semitransparente detrás del texto
+    canvas.fill_rect_alpha(x as i32, y as i32, 180, 40, [0, 0, 0], 128);
+
+    let left = (x + 5) as f32;
+    let fps_text = format!("FPS: {:.1}", fps);
+    let frame_text = format!("Frame: {:.1}ms", frame_time);
+    text.draw_text(canvas, (left, y as f32 + 4.0), &fps_text, 14.0, [255, 255, 255], text::Align::Left);
+    text.draw_text(canvas, (left, y as f32 + 20.0), &frame_text, 14.0, [255, 255, 255], text::Align::Left);
+}
+
+fn render_timer_overlay(canvas: &mut canvas::Canvas, text: &text::TextRenderer, elapsed_time: f32) {
+    let x = canvas.width - 180 - 10;
+    let y: u32 = 55;
+    canvas.fill_rect_alpha(x as i32, y as i32, 180, 20, [0, 0, 0], 128);
+
+    let timer_text = format!("Tiempo: {:.2} s", elapsed_time);
+    text.draw_text(canvas, ((x + 5) as f32, y as f32 + 2.0), &timer_text, 14.0, [255, 255, 0], text::Align::Left);
+}
+
+fn render_notice_overlay(canvas: &mut canvas::Canvas, text: &text::TextRenderer, notice: &str) {
+    let y = canvas.height - 22 - 10;
+    canvas.fill_rect_alpha(10, y as i32, canvas.width - 20, 22, [0, 0, 0], 128);
+    text.draw_text(canvas, (15.0, y as f32 + 3.0), notice, 14.0, [255, 255, 255], text::Align::Left);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{canvas, render_notice_overlay, text};
+
+    #[test]
+    fn notice_overlay_blends_over_the_frame() {
+        let (width, height) = (200, 60);
+        let mut pixels = [200, 100, 50, 0xFF].repeat((width * height) as usize);
+        let mut canvas = canvas::Canvas::new(&mut pixels, width, height);
+        render_notice_overlay(&mut canvas, &text::TextRenderer::bitmap(), "Captura guardada");
+
+        // Fondo oscurecido a la mitad, texto blanco encima y todo opaco
+        let px = |x: u32, y: u32| &pixels[((y * width + x) * 4) as usize..][..4];
+        assert_eq!(px(12, height - 30), [100, 50, 25, 255]);
+        assert_eq!(px(5, 5), [200, 100, 50, 255]);
+        assert!(pixels.chunks_exact(4).any(|px| px == [255, 255, 255, 255]));
+        assert!(pixels.chunks_exact(4).all(|px| px[3] == 0xFF));
     }
 }
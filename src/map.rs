@@ -1,7 +1,23 @@
+pub mod analysis;
+pub mod generate;
+
+use crate::fog::{Fog, FogMode};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
 pub struct Map {
     pub grid: Vec<Vec<u8>>, 
     pub start: (f32, f32),
     pub end: (usize, usize),
+    pub fog: Fog,
+}
+
+impl Default for Map {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Map {
@@ -24,9 +40,272 @@ impl Map {
         ];
         let start = (1.5, 1.5); // Entrada 
         let end = (9, 11);    // Salida (Meta)
-        Self { grid, start, end }
+        Self { grid, start, end, fog: Fog::default() }
+    }
+
+    // Carga un nivel en formato de texto (ver `FromStr`)
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, MapError> {
+        let text = std::fs::read_to_string(path).map_err(MapError::Io)?;
+        text.parse()
     }
+
     pub fn is_wall(&self, x: usize, y: usize) -> bool {
         self.grid.get(y).and_then(|row| row.get(x)).copied().unwrap_or(1) != 0
     }
-} 
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    Empty,
+    UnexpectedChar(char),
+    RaggedRow { expected: usize, found: usize },
+    NotEnclosed,
+    MissingStart,
+    MissingEnd,
+    DuplicateStart,
+    DuplicateEnd,
+    InvalidDirective(String),
+}
+
+// Error de formato con su posición (línea y columna comienzan en 1)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug)]
+pub enum MapError {
+    Io(std::io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::Empty => write!(f, "el nivel no tiene ninguna fila"),
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "carácter inesperado '{}'", c),
+            ParseErrorKind::RaggedRow { expected, found } => {
+                write!(f, "la fila tiene {} columnas pero se esperaban {}", found, expected)
+            }
+            ParseErrorKind::NotEnclosed => write!(f, "el borde del laberinto debe ser pared"),
+            ParseErrorKind::MissingStart => write!(f, "falta la entrada 'S'"),
+            ParseErrorKind::MissingEnd => write!(f, "falta la salida 'E'"),
+            ParseErrorKind::DuplicateStart => write!(f, "hay más de una entrada 'S'"),
+            ParseErrorKind::DuplicateEnd => write!(f, "hay más de una salida 'E'"),
+            ParseErrorKind::InvalidDirective(d) => write!(f, "directiva inválida '{}'", d),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "línea {}, columna {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::Io(e) => write!(f, "no se pudo leer el nivel: {}", e),
+            MapError::Parse(e) => write!(f, "nivel inválido en {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl std::error::Error for MapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MapError::Io(e) => Some(e),
+            MapError::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for MapError {
+    fn from(e: ParseError) -> Self {
+        MapError::Parse(e)
+    }
+}
+
+// Formato de nivel:
+//   `#` o `1`-`9`  pared (el dígito es el tipo de pared, `#` equivale a 1)
+//   `.`, `0`, ` `  pasillo
+//   `S` / `E`      entrada y salida (ambas son pasillo)
+//   `; ...`        comentario
+//   `@fog none` | `@fog linear <inicio> <fin>` | `@fog exp <densidad>`
+//   `@fog_color <r> <g> <b>`, `@light <atenuación>`
+// Las líneas en blanco se ignoran. Las filas deben tener el mismo ancho y el
+// borde exterior debe ser pared.
+impl FromStr for Map {
+    type Err = MapError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut grid: Vec<Vec<u8>> = Vec::new();
+        let mut rows_at: Vec<usize> = Vec::new(); // número de línea de cada fila
+        let mut start = None;
+        let mut end = None;
+        let mut fog = Fog::default();
+
+        for (i, line) in text.lines().enumerate() {
+            let line_no = i + 1;
+            let err = |column, kind| ParseError { line: line_no, column, kind };
+            if line.trim().is_empty() || line.trim_start().starts_with(';') {
+                continue;
+            }
+            if let Some(directive) = line.trim_start().strip_prefix('@') {
+                parse_directive(directive, &mut fog).map_err(|kind| err(1, kind))?;
+                continue;
+            }
+
+            let y = grid.len();
+            let mut row = Vec::new();
+            for (x, c) in line.chars().enumerate() {
+                let cell = match c {
+                    '#' => 1,
+                    '1'..='9' => c as u8 - b'0',
+                    '.' | '0' | ' ' => 0,
+                    'S' => {
+                        if start.replace((x, y)).is_some() {
+                            return Err(err(x + 1, ParseErrorKind::DuplicateStart).into());
+                        }
+                        0
+                    }
+                    'E' => {
+                        if end.replace((x, y)).is_some() {
+                            return Err(err(x + 1, ParseErrorKind::DuplicateEnd).into());
+                        }
+                        0
+                    }
+                    _ => return Err(err(x + 1, ParseErrorKind::UnexpectedChar(c)).into()),
+                };
+                row.push(cell);
+            }
+            if let Some(first) = grid.first() {
+                if row.len() != first.len() {
+                    let kind = ParseErrorKind::RaggedRow { expected: first.len(), found: row.len() };
+                    return Err(err(row.len().min(first.len()) + 1, kind).into());
+                }
+            }
+            grid.push(row);
+            rows_at.push(line_no);
+        }
+
+        let last_line = text.lines().count().max(1);
+        if grid.is_empty() {
+            return Err(ParseError { line: last_line, column: 1, kind: ParseErrorKind::Empty }.into());
+        }
+        let (h, w) = (grid.len(), grid[0].len());
+        for (y, row) in grid.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                let border = x == 0 || y == 0 || x == w - 1 || y == h - 1;
+                if border && cell == 0 {
+                    return Err(ParseError { line: rows_at[y], column: x + 1, kind: ParseErrorKind::NotEnclosed }.into());
+                }
+            }
+        }
+        let start = start.ok_or(ParseError { line: last_line, column: 1, kind: ParseErrorKind::MissingStart })?;
+        let end = end.ok_or(ParseError { line: last_line, column: 1, kind: ParseErrorKind::MissingEnd })?;
+
+        Ok(Self {
+            grid,
+            start: (start.0 as f32 + 0.5, start.1 as f32 + 0.5),
+            end,
+            fog,
+        })
+    }
+}
+
+fn parse_directive(directive: &str, fog: &mut Fog) -> Result<(), ParseErrorKind> {
+    let invalid = || ParseErrorKind::InvalidDirective(directive.trim().to_string());
+    let parts: Vec<&str> = directive.split_whitespace().collect();
+    let numbers = |args: &[&str]| -> Result<Vec<f32>, ParseErrorKind> {
+        args.iter().map(|a| a.parse().map_err(|_| invalid())).collect()
+    };
+    match parts.as_slice() {
+        ["fog", "none"] => fog.mode = FogMode::None,
+        ["fog", "linear", args @ ..] => match numbers(args)?[..] {
+            [start, end] => fog.mode = FogMode::Linear { start, end },
+            _ => return Err(invalid()),
+        },
+        ["fog", "exp", args @ ..] => match numbers(args)?[..] {
+            [density] if density >= 0.0 => fog.mode = FogMode::Exponential { density },
+            _ => return Err(invalid()),
+        },
+        ["fog_color", args @ ..] => match numbers(args)?[..] {
+            [r, g, b] if [r, g, b].iter().all(|c| (0.0..=255.0).contains(c)) => {
+                fog.color = [r as u8, g as u8, b as u8];
+            }
+            _ => return Err(invalid()),
+        },
+        ["light", args @ ..] => match numbers(args)?[..] {
+            [attenuation] if attenuation >= 0.0 => fog.attenuation = attenuation,
+            _ => return Err(invalid()),
+        },
+        _ => return Err(invalid()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Map, MapError, ParseError, ParseErrorKind};
+    use crate::fog::FogMode;
+
+    fn parse_error(text: &str) -> ParseError {
+        match text.parse::<Map>() {
+            Err(MapError::Parse(e)) => e,
+            Err(e) => panic!("error inesperado: {}", e),
+            Ok(_) => panic!("el nivel debería ser inválido"),
+        }
+    }
+
+    #[test]
+    fn parses_cells_start_end_and_directives() {
+        let map: Map = "; sala\n@fog linear 2 8\n@light 0.5\n#####\n#S.2#\n#..E#\n#####\n".parse().unwrap();
+        assert_eq!(map.grid[1], vec![1, 0, 0, 2, 1]);
+        assert_eq!(map.start, (1.5, 1.5));
+        assert_eq!(map.end, (3, 2));
+        assert_eq!(map.fog.mode, FogMode::Linear { start: 2.0, end: 8.0 });
+        assert_eq!(map.fog.attenuation, 0.5);
+    }
+
+    #[test]
+    fn bundled_level_matches_builtin_maze() {
+        let map = Map::load(concat!(env!("CARGO_MANIFEST_DIR"), "/levels/laberinto1.txt")).unwrap();
+        let builtin = Map::new();
+        assert_eq!(map.grid, builtin.grid);
+        assert_eq!(map.start, builtin.start);
+        assert_eq!(map.end, builtin.end);
+        assert!(Map::load(concat!(env!("CARGO_MANIFEST_DIR"), "/levels/cripta.txt")).is_ok());
+    }
+
+    #[test]
+    fn reports_line_and_column_of_errors() {
+        let e = parse_error("#####\n#S.x#\n#####\n");
+        assert_eq!((e.line, e.column, e.kind), (2, 4, ParseErrorKind::UnexpectedChar('x')));
+
+        let e = parse_error("#####\n#S.E#\n####\n");
+        assert_eq!((e.line, e.column), (3, 5));
+        assert_eq!(e.kind, ParseErrorKind::RaggedRow { expected: 5, found: 4 });
+
+        let e = parse_error("#####\n#S.E.\n#####\n");
+        assert_eq!((e.line, e.column, e.kind), (2, 5, ParseErrorKind::NotEnclosed));
+
+        let e = parse_error("#####\n#SSE#\n#####\n");
+        assert_eq!((e.line, e.column, e.kind), (2, 3, ParseErrorKind::DuplicateStart));
+
+        let e = parse_error("@fog thick\n#####\n#S.E#\n#####\n");
+        assert_eq!((e.line, e.kind), (1, ParseErrorKind::InvalidDirective("fog thick".into())));
+    }
+
+    #[test]
+    fn requires_start_and_end() {
+        assert_eq!(parse_error("#####\n#..E#\n#####\n").kind, ParseErrorKind::MissingStart);
+        assert_eq!(parse_error("#####\n#S..#\n#####\n").kind, ParseErrorKind::MissingEnd);
+        assert_eq!(parse_error("; vacío\n").kind, ParseErrorKind::Empty);
+    }
+}
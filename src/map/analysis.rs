@@ -0,0 +1,170 @@
+use super::Map;
+use std::collections::VecDeque;
+use std::fmt;
+
+// Problemas que impiden jugar un mapa correctamente
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    Empty,
+    NonRectangular { row: usize, expected: usize, found: usize },
+    NotEnclosed { x: usize, y: usize },
+    StartInWall { x: usize, y: usize },
+    GoalInWall { x: usize, y: usize },
+    UnreachableGoal,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Diagnostic::Empty => write!(f, "el mapa está vacío"),
+            Diagnostic::NonRectangular { row, expected, found } => {
+                write!(f, "la fila {} tiene {} columnas pero se esperaban {}", row, found, expected)
+            }
+            Diagnostic::NotEnclosed { x, y } => write!(f, "el borde tiene una abertura en ({}, {})", x, y),
+            Diagnostic::StartInWall { x, y } => write!(f, "la entrada ({}, {}) está dentro de una pared", x, y),
+            Diagnostic::GoalInWall { x, y } => write!(f, "la meta ({}, {}) está dentro de una pared", x, y),
+            Diagnostic::UnreachableGoal => write!(f, "la meta no es alcanzable desde la entrada"),
+        }
+    }
+}
+
+// Camino entre la entrada y la meta, incluyendo ambas celdas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPath {
+    pub cells: Vec<(usize, usize)>,
+}
+
+impl ShortestPath {
+    // Longitud en pasos entre celdas
+    pub fn steps(&self) -> usize {
+        self.cells.len() - 1
+    }
+}
+
+impl Map {
+    pub fn start_cell(&self) -> (usize, usize) {
+        (self.start.0.max(0.0) as usize, self.start.1.max(0.0) as usize)
+    }
+
+    // Revisa el mapa y devuelve todos los problemas encontrados (vacío si es válido)
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut issues = Vec::new();
+        let Some(first) = self.grid.first().filter(|row| !row.is_empty()) else {
+            return vec![Diagnostic::Empty];
+        };
+        let (w, h) = (first.len(), self.grid.len());
+        for (y, row) in self.grid.iter().enumerate() {
+            if row.len() != w {
+                issues.push(Diagnostic::NonRectangular { row: y, expected: w, found: row.len() });
+            }
+            for (x, &cell) in row.iter().enumerate() {
+                let border = x == 0 || y == 0 || x == row.len() - 1 || y == h - 1;
+                if border && cell == 0 {
+                    issues.push(Diagnostic::NotEnclosed { x, y });
+                }
+            }
+        }
+
+        let (sx, sy) = self.start_cell();
+        let (ex, ey) = self.end;
+        let start_ok = !self.is_wall(sx, sy);
+        let goal_ok = !self.is_wall(ex, ey);
+        if !start_ok {
+            issues.push(Diagnostic::StartInWall { x: sx, y: sy });
+        }
+        if !goal_ok {
+            issues.push(Diagnostic::GoalInWall { x: ex, y: ey });
+        }
+        if start_ok && goal_ok && self.shortest_path().is_none() {
+            issues.push(Diagnostic::UnreachableGoal);
+        }
+        issues
+    }
+
+    // Camino más corto (BFS en 4 direcciones) de la entrada a la meta
+    pub fn shortest_path(&self) -> Option<ShortestPath> {
+        let start = self.start_cell();
+        let goal = self.end;
+        if self.is_wall(start.0, start.1) || self.is_wall(goal.0, goal.1) {
+            return None;
+        }
+        let mut came_from = vec![vec![None; self.grid.iter().map(Vec::len).max().unwrap_or(0)]; self.grid.len()];
+        came_from[start.1][start.0] = Some(start);
+        let mut queue = VecDeque::from([start]);
+        while let Some((x, y)) = queue.pop_front() {
+            if (x, y) == goal {
+                let mut cells = vec![goal];
+                let mut cur = goal;
+                while cur != start {
+                    cur = came_from[cur.1][cur.0]?;
+                    cells.push(cur);
+                }
+                cells.reverse();
+                return Some(ShortestPath { cells });
+            }
+            let neighbors = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbors {
+                if !self.is_wall(nx, ny) && came_from[ny][nx].is_none() {
+                    came_from[ny][nx] = Some((x, y));
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Diagnostic;
+    use crate::map::Map;
+
+    #[test]
+    fn builtin_maze_is_valid_and_solvable() {
+        let map = Map::new();
+        assert!(map.validate().is_empty());
+        let path = map.shortest_path().unwrap();
+        assert_eq!(path.cells.first(), Some(&(1, 1)));
+        assert_eq!(path.cells.last(), Some(&map.end));
+        // Cada paso avanza exactamente una celda por un pasillo
+        for pair in path.cells.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+            assert!(!map.is_wall(b.0, b.1));
+        }
+        assert_eq!(path.steps(), path.cells.len() - 1);
+    }
+
+    #[test]
+    fn finds_shortest_of_several_routes() {
+        let map: Map = "#######\n#S....#\n#.###.#\n#....E#\n#######\n".parse().unwrap();
+        assert_eq!(map.shortest_path().unwrap().steps(), 6);
+    }
+
+    #[test]
+    fn reports_structural_problems() {
+        let mut map: Map = "#####\n#S#E#\n#####\n".parse().unwrap();
+        assert_eq!(map.validate(), vec![Diagnostic::UnreachableGoal]);
+        assert!(map.shortest_path().is_none());
+
+        map.start = (2.5, 1.5);
+        assert_eq!(map.validate(), vec![Diagnostic::StartInWall { x: 2, y: 1 }]);
+
+        map.grid[0][1] = 0;
+        map.grid[2].pop();
+        map.start = (1.5, 1.5);
+        assert_eq!(
+            map.validate(),
+            vec![
+                Diagnostic::NotEnclosed { x: 1, y: 0 },
+                Diagnostic::NonRectangular { row: 2, expected: 5, found: 4 },
+                Diagnostic::UnreachableGoal,
+            ]
+        );
+    }
+}
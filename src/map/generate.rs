@@ -0,0 +1,309 @@
+use super::Map;
+use crate::fog::Fog;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Backtracker,
+    Prim,
+    Kruskal,
+}
+
+impl Algorithm {
+    pub const NAMES: [&'static str; 3] = ["backtracker", "prim", "kruskal"];
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "backtracker" => Ok(Algorithm::Backtracker),
+            "prim" => Ok(Algorithm::Prim),
+            "kruskal" => Ok(Algorithm::Kruskal),
+            _ => Err(format!(
+                "algoritmo desconocido '{}' (opciones: {})",
+                name,
+                Algorithm::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Algorithm::Backtracker => "backtracker",
+            Algorithm::Prim => "prim",
+            Algorithm::Kruskal => "kruskal",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Generador SplitMix64: pequeño, rápido y reproducible en cualquier plataforma
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Entero uniforme en 0..n (n > 0)
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// Genera un laberinto perfecto de `width` x `height` celdas. La cuadrícula
+// resultante mide (2·width + 1) x (2·height + 1): las celdas ocupan las
+// posiciones impares y las paredes entre ellas las pares. La entrada y la
+// salida son los extremos del camino más largo del laberinto.
+pub fn generate(width: usize, height: usize, algorithm: Algorithm, seed: u64) -> Map {
+    assert!(width > 0 && height > 0, "el laberinto debe tener al menos una celda");
+    let mut maze = Carver::new(width, height);
+    let mut rng = Rng::new(seed);
+    match algorithm {
+        Algorithm::Backtracker => backtracker(&mut maze, &mut rng),
+        Algorithm::Prim => prim(&mut maze, &mut rng),
+        Algorithm::Kruskal => kruskal(&mut maze, &mut rng),
+    }
+
+    let grid = maze.grid;
+    let (a, _) = farthest_cell(&grid, (1, 1));
+    let (b, _) = farthest_cell(&grid, a);
+    Map {
+        grid,
+        start: (a.0 as f32 + 0.5, a.1 as f32 + 0.5),
+        end: b,
+        fog: Fog::default(),
+    }
+}
+
+struct Carver {
+    width: usize,
+    height: usize,
+    grid: Vec<Vec<u8>>,
+}
+
+impl Carver {
+    fn new(width: usize, height: usize) -> Self {
+        let mut grid = vec![vec![1; 2 * width + 1]; 2 * height + 1];
+        for cy in 0..height {
+            for cx in 0..width {
+                grid[2 * cy + 1][2 * cx + 1] = 0;
+            }
+        }
+        Self { width, height, grid }
+    }
+
+    fn index(&self, (cx, cy): (usize, usize)) -> usize {
+        cy * self.width + cx
+    }
+
+    fn neighbors(&self, (cx, cy): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut out = Vec::with_capacity(4);
+        if cx > 0 { out.push((cx - 1, cy)); }
+        if cy > 0 { out.push((cx, cy - 1)); }
+        if cx + 1 < self.width { out.push((cx + 1, cy)); }
+        if cy + 1 < self.height { out.push((cx, cy + 1)); }
+        out
+    }
+
+    // Abre la pared entre dos celdas adyacentes
+    fn carve(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let x = a.0 + b.0 + 1;
+        let y = a.1 + b.1 + 1;
+        self.grid[y][x] = 0;
+    }
+}
+
+// Búsqueda en profundidad con pila explícita (backtracking recursivo)
+fn backtracker(maze: &mut Carver, rng: &mut Rng) {
+    let mut visited = vec![false; maze.width * maze.height];
+    let first = (rng.below(maze.width), rng.below(maze.height));
+    visited[maze.index(first)] = true;
+    let mut stack = vec![first];
+    while let Some(&cell) = stack.last() {
+        let options: Vec<_> = maze
+            .neighbors(cell)
+            .into_iter()
+            .filter(|&n| !visited[maze.index(n)])
+            .collect();
+        if options.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let next = options[rng.below(options.len())];
+        visited[maze.index(next)] = true;
+        maze.carve(cell, next);
+        stack.push(next);
+    }
+}
+
+// Prim aleatorio: crece el árbol eligiendo una arista al azar de la frontera
+fn prim(maze: &mut Carver, rng: &mut Rng) {
+    let mut visited = vec![false; maze.width * maze.height];
+    let first = (rng.below(maze.width), rng.below(maze.height));
+    visited[maze.index(first)] = true;
+    let mut frontier: Vec<_> = maze.neighbors(first).into_iter().map(|n| (first, n)).collect();
+    while !frontier.is_empty() {
+        let (from, to) = frontier.swap_remove(rng.below(frontier.len()));
+        if visited[maze.index(to)] {
+            continue;
+        }
+        visited[maze.index(to)] = true;
+        maze.carve(from, to);
+        for n in maze.neighbors(to) {
+            if !visited[maze.index(n)] {
+                frontier.push((to, n));
+            }
+        }
+    }
+}
+
+// Kruskal aleatorio: recorre las aristas barajadas uniendo conjuntos disjuntos
+fn kruskal(maze: &mut Carver, rng: &mut Rng) {
+    let mut edges = Vec::new();
+    for cy in 0..maze.height {
+        for cx in 0..maze.width {
+            if cx + 1 < maze.width { edges.push(((cx, cy), (cx + 1, cy))); }
+            if cy + 1 < maze.height { edges.push(((cx, cy), (cx, cy + 1))); }
+        }
+    }
+    rng.shuffle(&mut edges);
+
+    let mut parent: Vec<usize> = (0..maze.width * maze.height).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for (a, b) in edges {
+        let ra = find(&mut parent, maze.index(a));
+        let rb = find(&mut parent, maze.index(b));
+        if ra != rb {
+            parent[ra] = rb;
+            maze.carve(a, b);
+        }
+    }
+}
+
+// Celda libre más lejana (en pasos) desde `from`, mediante BFS
+fn farthest_cell(grid: &[Vec<u8>], from: (usize, usize)) -> ((usize, usize), usize) {
+    let mut dist = vec![vec![usize::MAX; grid[0].len()]; grid.len()];
+    let mut queue = VecDeque::from([from]);
+    dist[from.1][from.0] = 0;
+    let mut best = (from, 0);
+    while let Some((x, y)) = queue.pop_front() {
+        let d = dist[y][x];
+        if d > best.1 {
+            best = ((x, y), d);
+        }
+        for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            if grid[ny][nx] == 0 && dist[ny][nx] == usize::MAX {
+                dist[ny][nx] = d + 1;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{farthest_cell, generate, Algorithm};
+
+    const ALL: [Algorithm; 3] = [Algorithm::Backtracker, Algorithm::Prim, Algorithm::Kruskal];
+
+    fn reachable(grid: &[Vec<u8>], from: (usize, usize)) -> usize {
+        let mut seen = vec![vec![false; grid[0].len()]; grid.len()];
+        let mut stack = vec![from];
+        let mut count = 0;
+        while let Some((x, y)) = stack.pop() {
+            if grid[y][x] != 0 || seen[y][x] {
+                continue;
+            }
+            seen[y][x] = true;
+            count += 1;
+            stack.extend([(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]);
+        }
+        count
+    }
+
+    #[test]
+    fn same_seed_yields_same_map() {
+        for algorithm in ALL {
+            let a = generate(9, 7, algorithm, 42);
+            let b = generate(9, 7, algorithm, 42);
+            assert_eq!(a.grid, b.grid);
+            assert_eq!(a.start, b.start);
+            assert_eq!(a.end, b.end);
+            assert_ne!(a.grid, generate(9, 7, algorithm, 43).grid);
+        }
+    }
+
+    #[test]
+    fn mazes_are_perfect() {
+        for algorithm in ALL {
+            for (w, h) in [(1, 1), (1, 6), (5, 5), (12, 4)] {
+                let map = generate(w, h, algorithm, 7);
+                assert_eq!(map.grid.len(), 2 * h + 1);
+                assert!(map.grid.iter().all(|row| row.len() == 2 * w + 1));
+                // Un árbol de w·h celdas tiene exactamente w·h - 1 pasajes
+                let open = map.grid.iter().flatten().filter(|&&c| c == 0).count();
+                assert_eq!(open, w * h + (w * h - 1), "{algorithm} {w}x{h}");
+                // y todas las celdas son alcanzables desde la entrada
+                let start = (map.start.0 as usize, map.start.1 as usize);
+                assert_eq!(reachable(&map.grid, start), open);
+                let border_open = map.grid[0].iter().chain(map.grid[2 * h].iter()).any(|&c| c == 0)
+                    || map.grid.iter().any(|row| row[0] == 0 || row[2 * w] == 0);
+                assert!(!border_open);
+            }
+        }
+    }
+
+    #[test]
+    fn start_and_end_are_longest_path_endpoints() {
+        for algorithm in ALL {
+            let map = generate(10, 8, algorithm, 3);
+            let start = (map.start.0 as usize, map.start.1 as usize);
+            let (far, dist) = farthest_cell(&map.grid, start);
+            let (_, to_end) = farthest_cell(&map.grid, map.end);
+            assert_eq!(far, map.end);
+            assert_eq!(dist, to_end);
+            // Ninguna celda está más lejos de la salida que la entrada
+            for y in (1..map.grid.len()).step_by(2) {
+                for x in (1..map.grid[0].len()).step_by(2) {
+                    assert!(farthest_cell(&map.grid, (x, y)).1 <= dist);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parses_algorithm_names() {
+        for algorithm in ALL {
+            assert_eq!(algorithm.to_string().parse::<Algorithm>(), Ok(algorithm));
+        }
+        assert!("dfs".parse::<Algorithm>().is_err());
+    }
+}
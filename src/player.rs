@@ -1,11 +1,146 @@
+use crate::map::Map;
+
+pub const PLAYER_RADIUS: f32 = 0.2;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Player {
     pub x: f32,
     pub y: f32,
     pub angle: f32,
+    pub radius: f32, // el jugador choca como un círculo de este radio
 }
 
 impl Player {
     pub fn new(x: f32, y: f32, angle: f32) -> Self {
-        Self { x, y, angle }
+        Self { x, y, angle, radius: PLAYER_RADIUS }
+    }
+
+    // Desplaza al jugador deslizándolo a lo largo de las paredes. El movimiento
+    // se divide en pasos cortos para no atravesar esquinas con pasos grandes.
+    pub fn move_by(&mut self, dx: f32, dy: f32, map: &Map) {
+        let max_step = (self.radius * 0.5).max(0.01);
+        let steps = (dx.abs().max(dy.abs()) / max_step).ceil().max(1.0) as u32;
+        let (sx, sy) = (dx / steps as f32, dy / steps as f32);
+        for _ in 0..steps {
+            self.x += sx;
+            self.push_out_of_walls(map);
+            self.y += sy;
+            self.push_out_of_walls(map);
+        }
+    }
+
+    // Separa el círculo de las celdas sólidas que lo tocan, empezando por la
+    // más cercana y empujándolo desde su punto más próximo; así una pared
+    // recta lo empuja de frente y una esquina aislada lo desvía en diagonal
+    fn push_out_of_walls(&mut self, map: &Map) {
+        let r = self.radius;
+        for _ in 0..8 {
+            let (min_x, max_x) = ((self.x - r).floor() as isize, (self.x + r).floor() as isize);
+            let (min_y, max_y) = ((self.y - r).floor() as isize, (self.y + r).floor() as isize);
+            let mut nearest: Option<(f32, isize, isize)> = None;
+            for cy in min_y..=max_y {
+                for cx in min_x..=max_x {
+                    if !is_solid(map, cx, cy) {
+                        continue;
+                    }
+                    let near_x = self.x.clamp(cx as f32, cx as f32 + 1.0);
+                    let near_y = self.y.clamp(cy as f32, cy as f32 + 1.0);
+                    let dist = (self.x - near_x).hypot(self.y - near_y);
+                    if dist < r && nearest.is_none_or(|(d, _, _)| dist < d) {
+                        nearest = Some((dist, cx, cy));
+                    }
+                }
+            }
+            let Some((dist, cx, cy)) = nearest else {
+                break;
+            };
+            let (left, top) = (cx as f32, cy as f32);
+            if dist > 1e-6 {
+                let near_x = self.x.clamp(left, left + 1.0);
+                let near_y = self.y.clamp(top, top + 1.0);
+                let push = (r - dist) / dist;
+                self.x += (self.x - near_x) * push;
+                self.y += (self.y - near_y) * push;
+            } else {
+                // El centro quedó dentro de la celda: salir por la cara más cercana
+                let exits = [
+                    (self.x - left + r, -1.0, 0.0),
+                    (left + 1.0 - self.x + r, 1.0, 0.0),
+                    (self.y - top + r, 0.0, -1.0),
+                    (top + 1.0 - self.y + r, 0.0, 1.0),
+                ];
+                let (d, ex, ey) = exits.into_iter().fold(exits[0], |a, b| if b.0 < a.0 { b } else { a });
+                self.x += ex * d;
+                self.y += ey * d;
+            }
+        }
+    }
+}
+
+fn is_solid(map: &Map, x: isize, y: isize) -> bool {
+    x < 0 || y < 0 || map.is_wall(x as usize, y as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_solid, Player};
+    use crate::map::generate::Rng;
+    use crate::map::Map;
+
+    const EPS: f32 = 1e-4;
+
+    // Distancia del centro del jugador a la celda sólida más cercana
+    fn clearance(player: &Player, map: &Map) -> f32 {
+        let mut best = f32::INFINITY;
+        let (px, py) = (player.x.floor() as isize, player.y.floor() as isize);
+        for cy in py - 2..=py + 2 {
+            for cx in px - 2..=px + 2 {
+                if is_solid(map, cx, cy) {
+                    let nx = player.x.clamp(cx as f32, cx as f32 + 1.0);
+                    let ny = player.y.clamp(cy as f32, cy as f32 + 1.0);
+                    best = best.min((player.x - nx).hypot(player.y - ny));
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn random_walk_never_gets_closer_than_radius() {
+        let map = Map::new();
+        let mut rng = Rng::new(1);
+        for radius in [0.1, 0.2, 0.35, 0.49] {
+            let mut player = Player::new(map.start.0, map.start.1, 0.0);
+            player.radius = radius;
+            for _ in 0..5000 {
+                let angle = (rng.below(3600) as f32).to_radians() / 10.0;
+                let len = rng.below(100) as f32 / 100.0;
+                player.move_by(angle.cos() * len, angle.sin() * len, &map);
+                let c = clearance(&player, &map);
+                assert!(c >= radius - EPS, "radio {radius}: {c} en ({}, {})", player.x, player.y);
+            }
+        }
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn slides_along_walls() {
+        // Pasillo horizontal: moverse en diagonal contra la pared sigue avanzando en x
+        let map: Map = "#######\n#S...E#\n#######\n".parse().unwrap();
+        let mut player = Player::new(1.5, 1.5, 0.0);
+        player.move_by(1.0, 1.0, &map);
+        assert!((player.x - 2.5).abs() < EPS, "{} {}", player.x, player.y);
+        assert!((player.y - (2.0 - player.radius)).abs() < EPS);
+    }
+
+    #[test]
+    fn cannot_squeeze_between_diagonal_walls() {
+        // (1,1) y (2,2) solo se tocan por una esquina entre dos paredes
+        let map: Map = "#####\n#S#E#\n##..#\n#####\n".parse().unwrap();
+        let mut player = Player::new(1.5, 1.5, 0.0);
+        for _ in 0..20 {
+            player.move_by(0.1, 0.1, &map);
+        }
+        assert!(clearance(&player, &map) >= player.radius - EPS);
+        assert!(player.x < 2.0 && player.y < 2.0, "atravesó la esquina hasta ({}, {})", player.x, player.y);
+    }
+}
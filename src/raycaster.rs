@@ -1,20 +1,213 @@
-pub fn cast_ray(player_x: f32, player_y: f32, angle: f32, map: &crate::map::Map, max_depth: f32) -> (f32, u8) {
-    let mut dist = 0.0;
-    let mut hit_wall = false;
-    let mut wall_type = 0;
-    while dist < max_depth && !hit_wall {
-        let rx = player_x + angle.cos() * dist;
-        let ry = player_y + angle.sin() * dist;
-        let mx = rx as isize;
-        let my = ry as isize;
-        if mx < 0 || my < 0 || mx >= map.grid[0].len() as isize || my >= map.grid.len() as isize {
-            hit_wall = true;
-            wall_type = 1;
-        } else if map.grid[my as usize][mx as usize] != 0 {
-            hit_wall = true;
-            wall_type = map.grid[my as usize][mx as usize];
+// Cara de la pared que golpeó el rayo: `Vertical` cuando cruzó un borde en x
+// (caras este/oeste) y `Horizontal` cuando cruzó un borde en y (norte/sur).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitSide {
+    Vertical,
+    Horizontal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    pub distance: f32,         // distancia a lo largo del rayo
+    pub cell: (isize, isize),  // celda impactada (puede quedar fuera del mapa)
+    pub side: HitSide,
+    pub point: (f32, f32),     // punto de impacto en coordenadas del mundo
+    pub wall_x: f32,           // coordenada 0..1 a lo largo de la cara
+    pub wall_type: u8,
+}
+
+// Recorre la cuadrícula celda por celda (DDA) hasta la primera pared.
+// Devuelve `None` si no hay pared antes de `max_depth`.
+pub fn cast_ray(player_x: f32, player_y: f32, angle: f32, map: &crate::map::Map, max_depth: f32) -> Option<RayHit> {
+    let (dir_x, dir_y) = (angle.cos(), angle.sin());
+    let mut map_x = player_x.floor() as isize;
+    let mut map_y = player_y.floor() as isize;
+
+    // Distancia que recorre el rayo para cruzar una celda completa en cada eje
+    let delta_x = if dir_x == 0.0 { f32::INFINITY } else { (1.0 / dir_x).abs() };
+    let delta_y = if dir_y == 0.0 { f32::INFINITY } else { (1.0 / dir_y).abs() };
+
+    // Distancia hasta el primer borde de celda en cada eje
+    let (step_x, mut side_x) = if dir_x == 0.0 {
+        (0, f32::INFINITY)
+    } else if dir_x < 0.0 {
+        (-1, (player_x - map_x as f32) * delta_x)
+    } else {
+        (1, (map_x as f32 + 1.0 - player_x) * delta_x)
+    };
+    let (step_y, mut side_y) = if dir_y == 0.0 {
+        (0, f32::INFINITY)
+    } else if dir_y < 0.0 {
+        (-1, (player_y - map_y as f32) * delta_y)
+    } else {
+        (1, (map_y as f32 + 1.0 - player_y) * delta_y)
+    };
+
+    loop {
+        let (dist, side) = if side_x < side_y {
+            map_x += step_x;
+            let d = side_x;
+            side_x += delta_x;
+            (d, HitSide::Vertical)
+        } else {
+            map_y += step_y;
+            let d = side_y;
+            side_y += delta_y;
+            (d, HitSide::Horizontal)
+        };
+        if dist >= max_depth {
+            return None;
         }
-        dist += 0.02;
+        let wall_type = if map_x < 0 || map_y < 0 {
+            1
+        } else {
+            match map.grid.get(map_y as usize).and_then(|row| row.get(map_x as usize)) {
+                None => 1, // fuera del mapa: se trata como pared
+                Some(&cell) => cell,
+            }
+        };
+        if wall_type == 0 {
+            continue;
+        }
+
+        let point = (player_x + dir_x * dist, player_y + dir_y * dist);
+        // Se invierte la coordenada según la dirección para que la textura
+        // no se vea reflejada al mirar la cara desde el otro lado
+        let wall_x = match side {
+            HitSide::Vertical => {
+                let u = point.1 - point.1.floor();
+                if dir_x < 0.0 { 1.0 - u } else { u }
+            }
+            HitSide::Horizontal => {
+                let u = point.0 - point.0.floor();
+                if dir_y > 0.0 { 1.0 - u } else { u }
+            }
+        };
+        return Some(RayHit {
+            distance: dist,
+            cell: (map_x, map_y),
+            side,
+            point,
+            wall_x,
+            wall_type,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cast_ray, HitSide, RayHit};
+    use crate::fog::Fog;
+    use crate::map::Map;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_6, PI};
+
+    const EPS: f32 = 1e-4;
+
+    fn map_from(grid: Vec<Vec<u8>>) -> Map {
+        Map { grid, start: (1.5, 1.5), end: (1, 1), fog: Fog::none() }
+    }
+
+    // Sala abierta de 5x5 rodeada de paredes
+    fn open_room() -> Map {
+        map_from(vec![
+            vec![1, 1, 1, 1, 1],
+            vec![1, 0, 0, 0, 1],
+            vec![1, 0, 0, 0, 1],
+            vec![1, 0, 0, 0, 1],
+            vec![1, 1, 1, 1, 1],
+        ])
+    }
+
+    fn hit(x: f32, y: f32, angle: f32, map: &Map, max_depth: f32) -> RayHit {
+        cast_ray(x, y, angle, map, max_depth).expect("el rayo debería chocar con una pared")
+    }
+
+    #[test]
+    fn axis_aligned_rays_hit_room_walls() {
+        let map = open_room();
+        for (angle, side) in [
+            (0.0, HitSide::Vertical),
+            (FRAC_PI_2, HitSide::Horizontal),
+            (PI, HitSide::Vertical),
+            (-FRAC_PI_2, HitSide::Horizontal),
+        ] {
+            let h = hit(2.5, 2.5, angle, &map, 16.0);
+            assert!((h.distance - 1.5).abs() < EPS, "angle {angle}: {}", h.distance);
+            assert_eq!(h.wall_type, 1);
+            assert_eq!(h.side, side);
+        }
+        let h = hit(1.25, 2.5, PI, &map, 16.0);
+        assert!((h.distance - 0.25).abs() < EPS);
+        assert_eq!(h.cell, (0, 2));
+    }
+
+    #[test]
+    fn oblique_ray_matches_analytic_distance() {
+        let map = open_room();
+        // A 30° el rayo cruza x = 4 antes que y = 4
+        let h = hit(1.5, 1.5, FRAC_PI_6, &map, 16.0);
+        let expected = 2.5 / FRAC_PI_6.cos();
+        assert!((h.distance - expected).abs() < EPS, "{} vs {expected}", h.distance);
+        assert_eq!(h.side, HitSide::Vertical);
+        assert_eq!(h.cell, (4, 2));
+        let hit_y = 1.5 + 2.5 * FRAC_PI_6.tan();
+        assert!((h.point.0 - 4.0).abs() < EPS && (h.point.1 - hit_y).abs() < EPS);
+        assert!((h.wall_x - hit_y.fract()).abs() < EPS);
+
+        // A 60° el rayo cruza y = 4 antes que x = 4
+        let h = hit(1.5, 1.5, 2.0 * FRAC_PI_6, &map, 16.0);
+        let expected = 2.5 / (2.0 * FRAC_PI_6).sin();
+        assert!((h.distance - expected).abs() < EPS, "{} vs {expected}", h.distance);
+        assert_eq!(h.side, HitSide::Horizontal);
+        assert_eq!(h.cell, (2, 4));
+    }
+
+    #[test]
+    fn wall_x_is_mirrored_for_opposite_faces() {
+        let map = open_room();
+        // La misma fila y = 2.25 vista desde el oeste y desde el este
+        let east = hit(2.5, 2.25, 0.0, &map, 16.0);
+        let west = hit(2.5, 2.25, PI, &map, 16.0);
+        assert!((east.wall_x - 0.25).abs() < EPS);
+        assert!((west.wall_x - 0.75).abs() < EPS);
+    }
+
+    #[test]
+    fn reports_wall_type_of_hit_cell() {
+        let map = map_from(vec![
+            vec![1, 1, 1, 1, 1, 1],
+            vec![1, 0, 0, 3, 0, 1],
+            vec![1, 1, 1, 1, 1, 1],
+        ]);
+        let h = hit(1.2, 1.5, 0.0, &map, 16.0);
+        assert!((h.distance - 1.8).abs() < EPS);
+        assert_eq!(h.wall_type, 3);
+        assert_eq!(h.cell, (3, 1));
+    }
+
+    #[test]
+    fn stops_at_max_depth() {
+        let map = map_from(vec![
+            vec![1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+            vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            vec![1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+        ]);
+        assert_eq!(cast_ray(1.5, 1.5, 0.0, &map, 4.0), None);
+        let h = hit(1.5, 1.5, 0.0, &map, 64.0);
+        assert!((h.distance - 9.5).abs() < EPS);
+        assert_eq!(h.wall_type, 1);
+    }
+
+    #[test]
+    fn leaving_the_grid_counts_as_wall() {
+        let map = map_from(vec![vec![0, 0, 0], vec![0, 0, 0]]);
+        let h = hit(0.5, 0.5, 0.0, &map, 16.0);
+        assert!((h.distance - 2.5).abs() < EPS);
+        assert_eq!(h.wall_type, 1);
+        assert_eq!(h.cell, (3, 0));
+        let h = hit(0.5, 0.5, PI, &map, 16.0);
+        assert!((h.distance - 0.5).abs() < EPS);
+        assert_eq!(h.wall_type, 1);
+        assert_eq!(h.cell, (-1, 0));
     }
-    (dist, wall_type)
-} 
\ No newline at end of file
+}
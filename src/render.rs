@@ -1,35 +1,45 @@
-use crate::{WIDTH, HEIGHT, FOV, NUM_RAYS, MAX_DEPTH, GameState};
-use crate::raycaster::cast_ray;
+use crate::game::Game;
+use crate::raycaster::{cast_ray, HitSide};
+use crate::textures::TextureAtlas;
 
-pub fn render_3d_basic(frame: &mut [u8], state: &GameState) {
-    // Fondo cielo y piso
-    for y in 0..HEIGHT {
-        for x in 0..WIDTH {
-            let idx = ((y * WIDTH + x) * 4) as usize;
-            if y < HEIGHT / 2 {
-                frame[idx] = 120; frame[idx+1] = 180; frame[idx+2] = 255; // cielo
-            } else {
-                frame[idx] = 218; frame[idx+1] = 219; frame[idx+2] = 195; // piso
-            }
-            frame[idx+3] = 0xFF;
-        }
-    }
+pub const FOV: f32 = std::f32::consts::FRAC_PI_3;
+pub const MAX_DEPTH: f32 = 16.0;
+const SKY_COLOR: [u8; 3] = [120, 180, 255];
+const FLOOR_COLOR: [u8; 3] = [218, 219, 195];
+
+// Se lanza un rayo por columna de pantalla
+fn ray_angle(game: &Game, ray: u32, width: u32) -> f32 {
+    game.player.angle - FOV/2.0 + FOV * (ray as f32) / (width as f32)
+}
+
+// Dibuja la vista 3D y el minimapa en un búfer RGBA de `width`x`height`
+pub fn render_3d_basic(frame: &mut [u8], width: u32, height: u32, game: &Game, textures: &TextureAtlas) {
+    // Cielo y piso
+    render_floor_ceiling(frame, width, height, game, textures);
     // Raycasting
-    for ray in 0..NUM_RAYS {
-        let ray_angle = state.player.angle - FOV/2.0 + FOV * (ray as f32) / (NUM_RAYS as f32);
-        let (dist, wall_type) = cast_ray(state.player.x, state.player.y, ray_angle, &state.map, MAX_DEPTH);
-        let dist = dist * (state.player.angle - ray_angle).cos(); // corrección de distorsión
-        let wall_height = (HEIGHT as f32 / dist).min(HEIGHT as f32);
-        let start = ((HEIGHT as f32 - wall_height) / 2.0) as usize;
-        let end = ((HEIGHT as f32 + wall_height) / 2.0) as usize;
-        let color = match wall_type {
-            1 => [0, 82, 110],   // pared tipo 1
-            2 => [50, 200, 50],   // pared tipo 2
-            3 => [50, 50, 200],   // pared tipo 3
-            _ => [180, 180, 180], // otro
+    for ray in 0..width {
+        let ray_angle = ray_angle(game, ray, width);
+        let hit = match cast_ray(game.player.x, game.player.y, ray_angle, &game.map, MAX_DEPTH) {
+            Some(hit) => hit,
+            None => continue,
         };
-        for y in start..end.min(HEIGHT as usize) {
-            let idx = ((y as u32 * WIDTH + ray) * 4) as usize;
+        let dist = hit.distance * (game.player.angle - ray_angle).cos(); // corrección de distorsión
+        // Altura real de la columna; puede superar la pantalla al estar cerca de la pared
+        let line_height = height as f32 / dist;
+        let top = (height as f32 - line_height) / 2.0;
+        let start = top.max(0.0) as usize;
+        let end = ((height as f32 + line_height) / 2.0).min(height as f32) as usize;
+        // Las caras norte/sur se oscurecen para distinguirlas de las este/oeste
+        let shade = if hit.side == HitSide::Horizontal { 0.7 } else { 1.0 };
+        let texture = textures.wall(hit.wall_type);
+        let flat = wall_color(hit.wall_type);
+        for y in start..end {
+            let color = match texture {
+                Some(tex) => tex.sample(hit.wall_x, (y as f32 - top) / line_height),
+                None => flat,
+            };
+            let color = game.map.fog.apply(color.map(|c| (c as f32 * shade) as u8), hit.distance);
+            let idx = ((y as u32 * width + ray) * 4) as usize;
             frame[idx] = color[0];
             frame[idx+1] = color[1];
             frame[idx+2] = color[2];
@@ -37,19 +47,77 @@ pub fn render_3d_basic(frame: &mut [u8], state: &GameState) {
         }
     }
     // Renderizar minimapa
-    render_minimap(frame, state);
+    render_minimap(frame, width, height, game);
 }
 
-pub fn render_minimap(frame: &mut [u8], state: &GameState) {
+// Dibuja la vista en una imagen nueva, sin ventana ni GPU
+pub fn render_to_image(width: u32, height: u32, game: &Game, textures: &TextureAtlas) -> image::RgbaImage {
+    let mut frame = vec![0; (width * height * 4) as usize];
+    render_3d_basic(&mut frame, width, height, game, textures);
+    image::RgbaImage::from_raw(width, height, frame).expect("el búfer tiene el tamaño de la imagen")
+}
+
+// Proyecta cada fila bajo el horizonte sobre el piso (y sobre el techo las de
+// arriba) y muestrea la textura en el punto del mundo correspondiente
+fn render_floor_ceiling(frame: &mut [u8], width: u32, height: u32, game: &Game, textures: &TextureAtlas) {
+    let floor = textures.floor();
+    let ceiling = textures.ceiling();
+    let fog = &game.map.fog;
+    // Dirección de cada columna dividida entre el coseno de su desviación, para
+    // que multiplicada por la distancia perpendicular de la fila dé el desplazamiento;
+    // el tercer valor convierte esa distancia en la distancia real a lo largo del rayo
+    let columns: Vec<(f32, f32, f32)> = (0..width)
+        .map(|ray| {
+            let angle = ray_angle(game, ray, width);
+            let correction = (game.player.angle - angle).cos();
+            (angle.cos() / correction, angle.sin() / correction, 1.0 / correction)
+        })
+        .collect();
+    for y in 0..height {
+        // Se usa el centro del píxel para no dividir entre cero en el horizonte
+        let offset = y as f32 + 0.5 - height as f32 / 2.0;
+        let row_dist = 0.5 * height as f32 / offset.abs();
+        let (texture, flat) = if offset > 0.0 { (floor, FLOOR_COLOR) } else { (ceiling, SKY_COLOR) };
+        for x in 0..width {
+            let (dx, dy, stretch) = columns[x as usize];
+            let color = match texture {
+                Some(tex) => {
+                    let wx = game.player.x + dx * row_dist;
+                    let wy = game.player.y + dy * row_dist;
+                    tex.sample(wx - wx.floor(), wy - wy.floor())
+                }
+                None => flat,
+            };
+            let color = fog.apply(color, row_dist * stretch);
+            let idx = ((y * width + x) * 4) as usize;
+            frame[idx] = color[0];
+            frame[idx+1] = color[1];
+            frame[idx+2] = color[2];
+            frame[idx+3] = 0xFF;
+        }
+    }
+}
+
+// Color plano para los tipos de pared sin textura
+fn wall_color(wall_type: u8) -> [u8; 3] {
+    match wall_type {
+        1 => [0, 82, 110],   // pared tipo 1
+        2 => [50, 200, 50],   // pared tipo 2
+        3 => [50, 50, 200],   // pared tipo 3
+        _ => [180, 180, 180], // otro
+    }
+}
+
+pub fn render_minimap(frame: &mut [u8], width: u32, height: u32, game: &Game) {
     let map_scale = 6;
     let offset_x = 10;
     let offset_y = 10;
-    let map_h = state.map.grid.len();
-    let map_w = state.map.grid[0].len();
+    let map_h = game.map.grid.len();
+    let map_w = game.map.grid[0].len();
     // Dibujar el mapa
     for y in 0..map_h {
         for x in 0..map_w {
-            let color = match state.map.grid[y][x] {
+            let color = match game.map.grid[y][x] {
                 1 => [39, 75, 92],
                 2 => [50, 200, 50],
                 3 => [50, 50, 200],
@@ -59,8 +127,8 @@ pub fn render_minimap(frame: &mut [u8], state: &GameState) {
                 for dx in 0..map_scale {
                     let px = offset_x + x * map_scale + dx;
                     let py = offset_y + y * map_scale + dy;
-                    if px < WIDTH as usize && py < HEIGHT as usize {
-                        let idx = ((py as u32 * WIDTH + px as u32) * 4) as usize;
+                    if px < width as usize && py < height as usize {
+                        let idx = ((py as u32 * width + px as u32) * 4) as usize;
                         frame[idx] = color[0];
                         frame[idx+1] = color[1];
                         frame[idx+2] = color[2];
@@ -71,15 +139,15 @@ pub fn render_minimap(frame: &mut [u8], state: &GameState) {
         }
     }
     // Dibujar la meta
-    let (goal_x, goal_y) = state.map.end;
+    let (goal_x, goal_y) = game.map.end;
     let px = offset_x + goal_x * map_scale;
     let py = offset_y + goal_y * map_scale;
     for dy in 0..map_scale {
         for dx in 0..map_scale {
             let x = px + dx;
             let y = py + dy;
-            if x < WIDTH as usize && y < HEIGHT as usize {
-                let idx = ((y as u32 * WIDTH + x as u32) * 4) as usize;
+            if x < width as usize && y < height as usize {
+                let idx = ((y as u32 * width + x as u32) * 4) as usize;
                 frame[idx] = 80;
                 frame[idx+1] = 200;
                 frame[idx+2] = 255;
@@ -88,14 +156,14 @@ pub fn render_minimap(frame: &mut [u8], state: &GameState) {
         }
     }
     // Dibujar al jugador
-    let px = offset_x + (state.player.x * map_scale as f32) as usize;
-    let py = offset_y + (state.player.y * map_scale as f32) as usize;
+    let px = offset_x + (game.player.x * map_scale as f32) as usize;
+    let py = offset_y + (game.player.y * map_scale as f32) as usize;
     for dy in 0..map_scale {
         for dx in 0..map_scale {
             let x = px + dx;
             let y = py + dy;
-            if x < WIDTH as usize && y < HEIGHT as usize {
-                let idx = ((y as u32 * WIDTH + x as u32) * 4) as usize;
+            if x < width as usize && y < height as usize {
+                let idx = ((y as u32 * width + x as u32) * 4) as usize;
                 frame[idx] = 255;
                 frame[idx+1] = 255;
                 frame[idx+2] = 0;
@@ -106,16 +174,38 @@ pub fn render_minimap(frame: &mut [u8], state: &GameState) {
     // Dibujar la dirección del jugador
     let dir_len = (map_scale * 2) as f32;
     for i in 0..dir_len as usize {
-        let x = px as f32 + state.player.angle.cos() * i as f32;
-        let y = py as f32 + state.player.angle.sin() * i as f32;
+        let x = px as f32 + game.player.angle.cos() * i as f32;
+        let y = py as f32 + game.player.angle.sin() * i as f32;
         let x = x as usize;
         let y = y as usize;
-        if x < WIDTH as usize && y < HEIGHT as usize {
-            let idx = ((y as u32 * WIDTH + x as u32) * 4) as usize;
+        if x < width as usize && y < height as usize {
+            let idx = ((y as u32 * width + x as u32) * 4) as usize;
             frame[idx] = 255;
             frame[idx+1] = 255;
             frame[idx+2] = 255;
             frame[idx+3] = 0xFF;
         }
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_to_image, wall_color, FLOOR_COLOR, SKY_COLOR};
+    use crate::game::Game;
+    use crate::textures::TextureAtlas;
+
+    #[test]
+    fn wall_height_matches_distance() {
+        // Pared a 2.5 unidades al este: mide 100 / 2.5 = 40 píxeles de alto
+        let map = "@fog none\n@light 0\n#####\n#S.E#\n#####\n".parse().unwrap();
+        let image = render_to_image(200, 100, &Game::new(map), &TextureAtlas::default());
+        let rgb = |y| {
+            let p = image.get_pixel(100, y);
+            [p[0], p[1], p[2]]
+        };
+        assert_eq!(rgb(25), SKY_COLOR);
+        assert_eq!(rgb(31), wall_color(1));
+        assert_eq!(rgb(68), wall_color(1));
+        assert_eq!(rgb(75), FLOOR_COLOR);
+    }
+}
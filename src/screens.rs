@@ -1,78 +1,19 @@
-use rusttype::{Font, Scale, point};
-use std::fs;
-use image::{self, GenericImageView, Pixel};
-
-fn draw_multiline_text_centered(
-    frame: &mut [u8],
-    font: &Font,
-    lines: &[&str],
-    scale: Scale,
-    width: u32,
-    height: u32,
-    color: [u8; 3],
-) {
-    let v_metrics = font.v_metrics(scale);
-    let line_height = v_metrics.ascent - v_metrics.descent + v_metrics.line_gap;
-    let total_text_height = line_height * lines.len() as f32;
-    let mut y = (height as f32 - total_text_height) / 2.0 + v_metrics.ascent;
-
-    for line in lines {
-        let glyphs: Vec<_> = font.layout(line, scale, point(0.0, 0.0)).collect();
-        let text_width: f32 = glyphs.iter().map(|g| g.unpositioned().h_metrics().advance_width).sum();
-        let x = (width as f32 - text_width) / 2.0;
-        for glyph in font.layout(line, scale, point(x, y)) {
-            if let Some(bb) = glyph.pixel_bounding_box() {
-                glyph.draw(|gx, gy, v| {
-                    let px = gx as i32 + bb.min.x;
-                    let py = gy as i32 + bb.min.y;
-                    if px >= 0 && px < width as i32 && py >= 0 && py < height as i32 {
-                        let idx = ((py as u32 * width + px as u32) * 4) as usize;
-                        let alpha = (v * 255.0) as u8;
-                        frame[idx] = color[0];
-                        frame[idx + 1] = color[1];
-                        frame[idx + 2] = color[2];
-                        frame[idx + 3] = alpha;
-                    }
-                });
-            }
-        }
+use crate::canvas::Canvas;
+use crate::image_cache::ImageCache;
+use crate::text::{Align, TextRenderer};
+use proyecto::assets::ImageId;
+
+// Dibuja varias líneas centradas en pantalla, cada una con su color
+fn draw_colored_lines_centered(canvas: &mut Canvas, text: &TextRenderer, lines: &[(&str, [u8; 3])], size: f32) {
+    let line_height = text.line_height(size);
+    let mut y = (canvas.height as f32 - line_height * lines.len() as f32) / 2.0;
+    let center = canvas.width as f32 / 2.0;
+    for (line, color) in lines {
+        text.draw_text(canvas, (center, y), line, size, *color, Align::Center);
         y += line_height;
     }
 }
 
-fn draw_image_centered(
-    frame: &mut [u8],
-    image_data: &image::DynamicImage,
-    width: u32,
-    height: u32,
-    target_width: u32,
-    target_height: u32,
-) {
-    let resized = image_data.resize(target_width, target_height, image::imageops::FilterType::Lanczos3);
-    
-    let x_offset = (width - target_width) / 2;
-    let y_offset = (height - target_height) / 2;
-    
-    // Dibujar la imagen en el frame
-    for y in 0..target_height {
-        for x in 0..target_width {
-            let pixel = resized.get_pixel(x, y);
-            let rgba = pixel.to_rgba();
-            
-            let frame_x = x_offset + x;
-            let frame_y = y_offset + y;
-            
-            if frame_x < width && frame_y < height {
-                let idx = ((frame_y * width + frame_x) * 4) as usize;
-                frame[idx] = rgba[0];     // R
-                frame[idx + 1] = rgba[1]; // G
-                frame[idx + 2] = rgba[2]; // B
-                frame[idx + 3] = rgba[3]; // A
-            }
-        }
-    }
-}
-
 // pub fn show_welcome_screen(frame: &mut [u8], width: u32, height: u32) {
 //     // Fondo azul oscuro
 //     for y in 0..height {
@@ -102,98 +43,76 @@ fn draw_image_centered(
 //     draw_multiline_text_centered(frame, &font, &lines, scale, width, height, [255, 255, 255]);
 // }
 
-pub fn show_welcome_screen(frame: &mut [u8], width: u32, height: u32) {
-
-    for y in 0..height {
-        for x in 0..width {
-            let idx = ((y * width + x) * 4) as usize;
-            frame[idx] = 0;
-            frame[idx + 1] = 0;
-            frame[idx + 2] = 0;
-            frame[idx + 3] = 0xFF;
-        }
+pub fn show_welcome_screen(canvas: &mut Canvas, text: &TextRenderer, images: &ImageCache) {
+    for px in canvas.pixels.chunks_exact_mut(4) {
+        px.copy_from_slice(&[0, 0, 0, 0xFF]);
     }
+    images.draw_centered(canvas, ImageId::Welcome);
 
-    if let Ok(image_data) = image::open("assets/welcome.png") {
-        // Obtener dimensiones originales de la imagen
-        let (img_w, img_h) = image_data.dimensions();
-        let aspect_ratio = img_w as f32 / img_h as f32;
-
-        let (target_width, target_height) = if width as f32 / height as f32 > aspect_ratio {
-            let h = height;
-            let w = (aspect_ratio * h as f32).round() as u32;
-            (w, h)
-        } else {
-            let w = width;
-            let h = (w as f32 / aspect_ratio).round() as u32;
-            (w, h)
-        };
+    draw_lines_at_bottom(canvas, text, &["L: mejores tiempos"], 18.0, [200, 200, 200]);
+}
 
-        // Dibujar la imagen centrada y redimensionada
-        draw_image_centered(frame, &image_data, width, height, target_width, target_height);
+pub fn show_success_screen(canvas: &mut Canvas, text: &TextRenderer, images: &ImageCache, stats: &[String]) {
+    for px in canvas.pixels.chunks_exact_mut(4) {
+        px.copy_from_slice(&[0, 0, 0, 0xFF]);
     }
+    images.draw_centered(canvas, ImageId::Success);
+
+    // Resumen de la partida sobre una franja oscura en el centro
+    let band_height = (stats.len() as u32 + 1) * 30;
+    let band_top = canvas.height.saturating_sub(band_height) / 2;
+    canvas.fill_rect_alpha(0, band_top as i32, canvas.width, band_height, [0, 0, 0], 192);
+    let mut stat_lines = vec![("¡Llegaste a la meta!", [255, 255, 0])];
+    stat_lines.extend(stats.iter().map(|line| (line.as_str(), [255, 255, 255])));
+    draw_colored_lines_centered(canvas, text, &stat_lines, 24.0);
+
+    let lines = [
+        "R: repetir   N: siguiente nivel   G: laberinto nuevo",
+        "Presiona ESC para salir",
+    ];
+    draw_lines_at_bottom(canvas, text, &lines, 22.0, [255, 255, 255]);
 }
 
-pub fn show_success_screen(frame: &mut [u8], width: u32, height: u32) {
-
-    for y in 0..height {
-        for x in 0..width {
-            let idx = ((y * width + x) * 4) as usize;
-            frame[idx] = 0;
-            frame[idx + 1] = 0;
-            frame[idx + 2] = 0;
-            frame[idx + 3] = 0xFF;
-        }
+// Dibuja líneas centradas horizontalmente al pie de la pantalla
+fn draw_lines_at_bottom(canvas: &mut Canvas, text: &TextRenderer, lines: &[&str], size: f32, color: [u8; 3]) {
+    let line_height = text.line_height(size);
+    let mut y = canvas.height as f32 - line_height * (lines.len() as f32 + 0.5);
+    let center = canvas.width as f32 / 2.0;
+    for line in lines {
+        text.draw_text(canvas, (center, y), line, size, color, Align::Center);
+        y += line_height;
     }
+}
 
-    if let Ok(image_data) = image::open("assets/success_image.png") {
-        // Obtener dimensiones originales de la imagen
-        let (img_w, img_h) = image_data.dimensions();
-        let aspect_ratio = img_w as f32 / img_h as f32;
-
-        let (target_width, target_height) = if width as f32 / height as f32 > aspect_ratio {
-            let h = height;
-            let w = (aspect_ratio * h as f32).round() as u32;
-            (w, h)
-        } else {
-            let w = width;
-            let h = (w as f32 / aspect_ratio).round() as u32;
-            (w, h)
-        };
-
-        draw_image_centered(frame, &image_data, width, height, target_width, target_height);
+pub fn show_leaderboard(canvas: &mut Canvas, text: &TextRenderer, rows: &[String]) {
+    for px in canvas.pixels.chunks_exact_mut(4) {
+        px.copy_from_slice(&[10, 15, 40, 0xFF]);
     }
 
-    let font_data = std::fs::read("assets/DejaVuSans.ttf").expect("Error al leer la fuente");
-    let font = rusttype::Font::try_from_vec(font_data).expect("Error al cargar la fuente");
-
-    let lines = ["Presiona ESC para salir"];
-    let scale = rusttype::Scale::uniform(32.0);
-
-    let v_metrics = font.v_metrics(scale);
-    let line_height = v_metrics.ascent - v_metrics.descent + v_metrics.line_gap;
-    let y = height as f32 - line_height * 2.0; 
+    let mut lines = vec![("Mejores tiempos", [255, 255, 0]), ("", [255, 255, 255])];
+    if rows.is_empty() {
+        lines.push(("Aún no hay marcas para este laberinto", [200, 200, 200]));
+    }
+    lines.extend(rows.iter().map(|row| (row.as_str(), [255, 255, 255])));
+    lines.push(("", [255, 255, 255]));
+    lines.push(("Presiona ESC para volver", [160, 160, 160]));
+    draw_colored_lines_centered(canvas, text, &lines, 22.0);
+}
 
-    for line in lines.iter() {
-        let glyphs: Vec<_> = font.layout(line, scale, rusttype::point(0.0, 0.0)).collect();
-        let text_width: f32 = glyphs.iter().map(|g| g.unpositioned().h_metrics().advance_width).sum();
-        let x = (width as f32 - text_width) / 2.0;
-        for glyph in font.layout(line, scale, rusttype::point(x, y)) {
-            if let Some(bb) = glyph.pixel_bounding_box() {
-                glyph.draw(|gx, gy, v| {
-                    let px = gx as i32 + bb.min.x;
-                    let py = gy as i32 + bb.min.y;
-                    if px >= 0 && px < width as i32 && py >= 0 && py < height as i32 {
-                        let idx = ((py as u32 * width + px as u32) * 4) as usize;
-                        let alpha = (v * 255.0) as u8;
-                        frame[idx] = 255;
-                        frame[idx + 1] = 255;
-                        frame[idx + 2] = 255;
-                        frame[idx + 3] = alpha;
-                    }
-                });
-            }
-        }
+pub fn show_pause_menu(canvas: &mut Canvas, text: &TextRenderer, entries: &[&str], selected: usize) {
+    // Oscurecer la vista congelada
+    let (width, height) = (canvas.width, canvas.height);
+    canvas.fill_rect_alpha(0, 0, width, height, [0, 0, 0], 170);
+
+    let labels: Vec<String> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| if i == selected { format!("> {} <", entry) } else { entry.to_string() })
+        .collect();
+    let mut lines = vec![("PAUSA", [255, 255, 255]), ("", [255, 255, 255])];
+    for (i, label) in labels.iter().enumerate() {
+        let color = if i == selected { [255, 255, 0] } else { [200, 200, 200] };
+        lines.push((label.as_str(), color));
     }
+    draw_colored_lines_centered(canvas, text, &lines, 32.0);
 }
- 
\ No newline at end of file
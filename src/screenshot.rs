@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SCREENSHOTS_DIR: &str = "screenshots";
+
+// Guarda el cuadro RGBA en `screenshots/` con la fecha y hora (UTC) en el nombre
+pub fn save(frame: &[u8], width: u32, height: u32) -> Result<PathBuf, String> {
+    let dir = Path::new(SCREENSHOTS_DIR);
+    std::fs::create_dir_all(dir).map_err(|e| format!("No se pudo crear '{}': {}", dir.display(), e))?;
+    let path = dir.join(format!("captura_{}.png", timestamp(SystemTime::now())));
+    // La ventana ignora el alfa; se guarda opaco para que se vea igual que en pantalla
+    let mut pixels = frame.to_vec();
+    for px in pixels.chunks_exact_mut(4) {
+        px[3] = 0xFF;
+    }
+    let image = image::RgbaImage::from_raw(width, height, pixels)
+        .ok_or_else(|| "El cuadro no coincide con el tamaño de la ventana".to_string())?;
+    image.save(&path).map_err(|e| format!("No se pudo guardar '{}': {}", path.display(), e))?;
+    Ok(path)
+}
+
+// AAAAMMDD_HHMMSS_mmm; los milisegundos evitan pisar capturas seguidas
+fn timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let day_secs = secs % 86_400;
+    format!(
+        "{:04}{:02}{:02}_{:02}{:02}{:02}_{:03}",
+        year,
+        month,
+        day,
+        day_secs / 3600,
+        day_secs / 60 % 60,
+        day_secs % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+// Fecha del calendario gregoriano a partir de los días desde 1970-01-01
+// (algoritmo de Howard Hinnant)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::timestamp;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn timestamp_formats_utc_date_and_time() {
+        assert_eq!(timestamp(UNIX_EPOCH), "19700101_000000_000");
+        // 29 de febrero de 2024, 13:45:07.250
+        let leap = UNIX_EPOCH + Duration::from_millis(1_709_214_307_250);
+        assert_eq!(timestamp(leap), "20240229_134507_250");
+    }
+}
@@ -0,0 +1,301 @@
+use crate::canvas::Canvas;
+use rusttype::{point, Font, Scale};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+const ATLAS_WIDTH: u32 = 512;
+
+// Alineación horizontal respecto de la posición indicada
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+}
+
+enum FontSource {
+    TrueType(Font<'static>),
+    Bitmap, // fuente integrada de 5x7 para cuando falta el archivo
+}
+
+// Glifo ya rasterizado dentro del atlas
+#[derive(Debug, Clone, Copy)]
+struct AtlasGlyph {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    left: i32, // desplazamiento desde la posición del lápiz
+    top: i32,  // desplazamiento desde la línea base (negativo hacia arriba)
+    advance: f32,
+}
+
+// Cobertura de todos los glifos usados, acomodados en filas dentro de una
+// sola imagen de ancho fijo que crece hacia abajo
+struct GlyphAtlas {
+    coverage: Vec<u8>,
+    height: u32,
+    cursor: (u32, u32),
+    row_height: u32,
+    glyphs: HashMap<(char, u32), AtlasGlyph>,
+}
+
+impl GlyphAtlas {
+    fn new() -> Self {
+        Self { coverage: Vec::new(), height: 0, cursor: (0, 0), row_height: 0, glyphs: HashMap::new() }
+    }
+
+    // Reserva un lugar de `width`x`height` y copia ahí la cobertura del glifo
+    fn insert(&mut self, width: u32, height: u32, bitmap: &[u8]) -> (u32, u32) {
+        if self.cursor.0 + width > ATLAS_WIDTH {
+            self.cursor = (0, self.cursor.1 + self.row_height);
+            self.row_height = 0;
+        }
+        let (x, y) = self.cursor;
+        if y + height > self.height {
+            self.height = y + height;
+            self.coverage.resize((ATLAS_WIDTH * self.height) as usize, 0);
+        }
+        for row in 0..height {
+            let src = (row * width) as usize;
+            let dst = ((y + row) * ATLAS_WIDTH + x) as usize;
+            self.coverage[dst..dst + width as usize].copy_from_slice(&bitmap[src..src + width as usize]);
+        }
+        self.cursor.0 += width;
+        self.row_height = self.row_height.max(height);
+        (x, y)
+    }
+}
+
+pub struct TextRenderer {
+    font: FontSource,
+    atlas: RefCell<GlyphAtlas>,
+}
+
+impl TextRenderer {
+    // Sin fuente cargada se usa la fuente integrada
+    pub fn new(font: Option<Font<'static>>) -> Self {
+        match font {
+            Some(font) => Self::with_font(FontSource::TrueType(font)),
+            None => Self::bitmap(),
+        }
+    }
+
+    pub fn bitmap() -> Self {
+        Self::with_font(FontSource::Bitmap)
+    }
+
+    fn with_font(font: FontSource) -> Self {
+        Self { font, atlas: RefCell::new(GlyphAtlas::new()) }
+    }
+
+    // Distancia entre líneas consecutivas
+    pub fn line_height(&self, size: f32) -> f32 {
+        match &self.font {
+            FontSource::TrueType(font) => {
+                let v = font.v_metrics(Scale::uniform(size));
+                v.ascent - v.descent + v.line_gap
+            }
+            FontSource::Bitmap => 10.0 * bitmap_scale(size) as f32,
+        }
+    }
+
+    fn ascent(&self, size: f32) -> f32 {
+        match &self.font {
+            FontSource::TrueType(font) => font.v_metrics(Scale::uniform(size)).ascent,
+            FontSource::Bitmap => 8.0 * bitmap_scale(size) as f32,
+        }
+    }
+
+    // Ancho del texto en píxeles
+    pub fn measure(&self, text: &str, size: f32) -> f32 {
+        let mut width = 0.0;
+        let mut prev = None;
+        for c in text.chars() {
+            width += self.kerning(prev, c, size) + self.glyph(c, size).advance;
+            prev = Some(c);
+        }
+        width
+    }
+
+    // Dibuja una línea; `pos` es el borde superior de la línea y su borde
+    // izquierdo o su centro según `align`
+    pub fn draw_text(&self, canvas: &mut Canvas, pos: (f32, f32), text: &str, size: f32, color: [u8; 3], align: Align) {
+        let width = match align {
+            Align::Left => 0.0,
+            Align::Center => self.measure(text, size) / 2.0,
+        };
+        let mut pen_x = pos.0 - width;
+        let baseline = (pos.1 + self.ascent(size)).round() as i32;
+        let mut prev = None;
+        for c in text.chars() {
+            pen_x += self.kerning(prev, c, size);
+            prev = Some(c);
+            let glyph = self.glyph(c, size);
+            let atlas = self.atlas.borrow();
+            let left = pen_x.round() as i32 + glyph.left;
+            let top = baseline + glyph.top;
+            for gy in 0..glyph.height {
+                for gx in 0..glyph.width {
+                    let v = atlas.coverage[((glyph.y + gy) * ATLAS_WIDTH + glyph.x + gx) as usize];
+                    if v == 0 {
+                        continue;
+                    }
+                    canvas.blend_pixel(left + gx as i32, top + gy as i32, color, v);
+                }
+            }
+            pen_x += glyph.advance;
+        }
+    }
+
+    fn kerning(&self, prev: Option<char>, c: char, size: f32) -> f32 {
+        match (&self.font, prev) {
+            (FontSource::TrueType(font), Some(prev)) => font.pair_kerning(Scale::uniform(size), prev, c),
+            _ => 0.0,
+        }
+    }
+
+    // Busca el glifo en el atlas y lo rasteriza la primera vez que se usa
+    fn glyph(&self, c: char, size: f32) -> AtlasGlyph {
+        let key = (c, size.to_bits());
+        if let Some(glyph) = self.atlas.borrow().glyphs.get(&key) {
+            return *glyph;
+        }
+        let (width, height, left, top, advance, bitmap) = match &self.font {
+            FontSource::TrueType(font) => {
+                let glyph = font.glyph(c).scaled(Scale::uniform(size));
+                let advance = glyph.h_metrics().advance_width;
+                let glyph = glyph.positioned(point(0.0, 0.0));
+                match glyph.pixel_bounding_box() {
+                    Some(bb) => {
+                        let (w, h) = (bb.width() as u32, bb.height() as u32);
+                        let mut bitmap = vec![0; (w * h) as usize];
+                        glyph.draw(|x, y, v| bitmap[(y * w + x) as usize] = (v * 255.0) as u8);
+                        (w, h, bb.min.x, bb.min.y, advance, bitmap)
+                    }
+                    None => (0, 0, 0, 0, advance, Vec::new()),
+                }
+            }
+            FontSource::Bitmap => {
+                let s = bitmap_scale(size);
+                (5 * s, 8 * s, 0, -7 * s as i32, 6.0 * s as f32, rasterize_bitmap(c, s))
+            }
+        };
+        let mut atlas = self.atlas.borrow_mut();
+        let (x, y) = atlas.insert(width, height, &bitmap);
+        let glyph = AtlasGlyph { x, y, width, height, left, top, advance };
+        atlas.glyphs.insert(key, glyph);
+        glyph
+    }
+}
+
+// La fuente integrada solo se amplía en múltiplos enteros para verse nítida
+fn bitmap_scale(size: f32) -> u32 {
+    (size / 8.0).round().max(1.0) as u32
+}
+
+fn rasterize_bitmap(c: char, scale: u32) -> Vec<u8> {
+    let columns = bitmap_glyph(c);
+    let (w, h) = (5 * scale, 8 * scale);
+    let mut bitmap = vec![0; (w * h) as usize];
+    for y in 0..h {
+        for x in 0..w {
+            if columns[(x / scale) as usize] >> (y / scale) & 1 == 1 {
+                bitmap[(y * w + x) as usize] = 255;
+            }
+        }
+    }
+    bitmap
+}
+
+// Columnas del glifo (bit 0 arriba); las letras acentuadas usan la letra base
+fn bitmap_glyph(c: char) -> [u8; 5] {
+    let c = match c {
+        'á' | 'à' | 'ä' | 'â' => 'a',
+        'é' | 'è' | 'ë' | 'ê' => 'e',
+        'í' | 'ì' | 'ï' | 'î' => 'i',
+        'ó' | 'ò' | 'ö' | 'ô' => 'o',
+        'ú' | 'ù' | 'ü' | 'û' => 'u',
+        'Á' | 'À' | 'Ä' | 'Â' => 'A',
+        'É' | 'È' | 'Ë' | 'Ê' => 'E',
+        'Í' | 'Ì' | 'Ï' | 'Î' => 'I',
+        'Ó' | 'Ò' | 'Ö' | 'Ô' => 'O',
+        'Ú' | 'Ù' | 'Ü' | 'Û' => 'U',
+        'ñ' => 'n',
+        'Ñ' => 'N',
+        // Signos de apertura: los de cierre dados vuelta
+        '¡' => return bitmap_glyph('!').map(flip_column),
+        '¿' => return bitmap_glyph('?').map(flip_column),
+        c if (' '..='~').contains(&c) => c,
+        _ => '?',
+    };
+    BITMAP_FONT[(c as u8 - b' ') as usize]
+}
+
+// Invierte las 7 filas superiores (la octava es para los descendentes)
+fn flip_column(column: u8) -> u8 {
+    (0..7).fold(0, |flipped, row| flipped | ((column >> row) & 1) << (6 - row))
+}
+
+// ASCII imprimible de ' ' a '~', 5 columnas por carácter
+const BITMAP_FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], [0x00, 0x00, 0x5F, 0x00, 0x00], [0x00, 0x07, 0x00, 0x07, 0x00],
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], [0x24, 0x2A, 0x7F, 0x2A, 0x12], [0x23, 0x13, 0x08, 0x64, 0x62],
+    [0x36, 0x49, 0x56, 0x20, 0x50], [0x00, 0x08, 0x07, 0x03, 0x00], [0x00, 0x1C, 0x22, 0x41, 0x00],
+    [0x00, 0x41, 0x22, 0x1C, 0x00], [0x2A, 0x1C, 0x7F, 0x1C, 0x2A], [0x08, 0x08, 0x3E, 0x08, 0x08],
+    [0x00, 0x80, 0x70, 0x30, 0x00], [0x08, 0x08, 0x08, 0x08, 0x08], [0x00, 0x00, 0x60, 0x60, 0x00],
+    [0x20, 0x10, 0x08, 0x04, 0x02], [0x3E, 0x51, 0x49, 0x45, 0x3E], [0x00, 0x42, 0x7F, 0x40, 0x00],
+    [0x72, 0x49, 0x49, 0x49, 0x46], [0x21, 0x41, 0x49, 0x4D, 0x33], [0x18, 0x14, 0x12, 0x7F, 0x10],
+    [0x27, 0x45, 0x45, 0x45, 0x39], [0x3C, 0x4A, 0x49, 0x49, 0x31], [0x41, 0x21, 0x11, 0x09, 0x07],
+    [0x36, 0x49, 0x49, 0x49, 0x36], [0x46, 0x49, 0x49, 0x29, 0x1E], [0x00, 0x00, 0x14, 0x00, 0x00],
+    [0x00, 0x40, 0x34, 0x00, 0x00], [0x00, 0x08, 0x14, 0x22, 0x41], [0x14, 0x14, 0x14, 0x14, 0x14],
+    [0x00, 0x41, 0x22, 0x14, 0x08], [0x02, 0x01, 0x59, 0x09, 0x06], [0x3E, 0x41, 0x5D, 0x59, 0x4E],
+    [0x7C, 0x12, 0x11, 0x12, 0x7C], [0x7F, 0x49, 0x49, 0x49, 0x36], [0x3E, 0x41, 0x41, 0x41, 0x22],
+    [0x7F, 0x41, 0x41, 0x41, 0x3E], [0x7F, 0x49, 0x49, 0x49, 0x41], [0x7F, 0x09, 0x09, 0x09, 0x01],
+    [0x3E, 0x41, 0x41, 0x51, 0x73], [0x7F, 0x08, 0x08, 0x08, 0x7F], [0x00, 0x41, 0x7F, 0x41, 0x00],
+    [0x20, 0x40, 0x41, 0x3F, 0x01], [0x7F, 0x08, 0x14, 0x22, 0x41], [0x7F, 0x40, 0x40, 0x40, 0x40],
+    [0x7F, 0x02, 0x1C, 0x02, 0x7F], [0x7F, 0x04, 0x08, 0x10, 0x7F], [0x3E, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x09, 0x09, 0x09, 0x06], [0x3E, 0x41, 0x51, 0x21, 0x5E], [0x7F, 0x09, 0x19, 0x29, 0x46],
+    [0x26, 0x49, 0x49, 0x49, 0x32], [0x03, 0x01, 0x7F, 0x01, 0x03], [0x3F, 0x40, 0x40, 0x40, 0x3F],
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], [0x3F, 0x40, 0x38, 0x40, 0x3F], [0x63, 0x14, 0x08, 0x14, 0x63],
+    [0x03, 0x04, 0x78, 0x04, 0x03], [0x61, 0x59, 0x49, 0x4D, 0x43], [0x00, 0x7F, 0x41, 0x41, 0x41],
+    [0x02, 0x04, 0x08, 0x10, 0x20], [0x00, 0x41, 0x41, 0x41, 0x7F], [0x04, 0x02, 0x01, 0x02, 0x04],
+    [0x40, 0x40, 0x40, 0x40, 0x40], [0x00, 0x03, 0x07, 0x08, 0x00], [0x20, 0x54, 0x54, 0x78, 0x40],
+    [0x7F, 0x28, 0x44, 0x44, 0x38], [0x38, 0x44, 0x44, 0x44, 0x28], [0x38, 0x44, 0x44, 0x28, 0x7F],
+    [0x38, 0x54, 0x54, 0x54, 0x18], [0x00, 0x08, 0x7E, 0x09, 0x02], [0x18, 0xA4, 0xA4, 0x9C, 0x78],
+    [0x7F, 0x08, 0x04, 0x04, 0x78], [0x00, 0x44, 0x7D, 0x40, 0x00], [0x20, 0x40, 0x40, 0x3D, 0x00],
+    [0x7F, 0x10, 0x28, 0x44, 0x00], [0x00, 0x41, 0x7F, 0x40, 0x00], [0x7C, 0x04, 0x78, 0x04, 0x78],
+    [0x7C, 0x08, 0x04, 0x04, 0x78], [0x38, 0x44, 0x44, 0x44, 0x38], [0xFC, 0x18, 0x24, 0x24, 0x18],
+    [0x18, 0x24, 0x24, 0x18, 0xFC], [0x7C, 0x08, 0x04, 0x04, 0x08], [0x48, 0x54, 0x54, 0x54, 0x24],
+    [0x04, 0x04, 0x3F, 0x44, 0x24], [0x3C, 0x40, 0x40, 0x20, 0x7C], [0x1C, 0x20, 0x40, 0x20, 0x1C],
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], [0x44, 0x28, 0x10, 0x28, 0x44], [0x4C, 0x90, 0x90, 0x90, 0x7C],
+    [0x44, 0x64, 0x54, 0x4C, 0x44], [0x00, 0x08, 0x36, 0x41, 0x00], [0x00, 0x00, 0x77, 0x00, 0x00],
+    [0x00, 0x41, 0x36, 0x08, 0x00], [0x02, 0x01, 0x02, 0x04, 0x02],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{Align, TextRenderer};
+    use crate::canvas::Canvas;
+
+    #[test]
+    fn bitmap_font_draws_aligned_text_and_caches_glyphs() {
+        let text = TextRenderer::bitmap();
+        // Escala 2: cada carácter avanza 12 píxeles
+        assert_eq!(text.measure("Año", 16.0), 36.0);
+        let mut pixels = vec![0; 100 * 40 * 4];
+        let mut canvas = Canvas::new(&mut pixels, 100, 40);
+        text.draw_text(&mut canvas, (76.0, 0.0), "ll", 16.0, [255, 0, 0], Align::Center);
+        // La 'l' ocupa la columna central de su celda: x = 76 - 24/2 + 2*2
+        let lit = |canvas: &Canvas, x, y| canvas.pixels[canvas.index(x, y).unwrap()] == 255;
+        assert!(lit(&canvas, 68, 4));
+        assert!(!lit(&canvas, 64, 4));
+        // 'A', 'ñ', 'o' y una sola entrada para las dos 'l'
+        assert_eq!(text.atlas.borrow().glyphs.len(), 4);
+    }
+
+    #[test]
+    fn unknown_characters_fall_back_to_question_mark() {
+        assert_eq!(super::bitmap_glyph('€'), super::bitmap_glyph('?'));
+        assert_eq!(super::bitmap_glyph('é'), super::bitmap_glyph('e'));
+    }
+}
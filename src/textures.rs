@@ -0,0 +1,59 @@
+use crate::assets::{Assets, ImageId};
+use image::RgbaImage;
+use std::collections::HashMap;
+
+pub struct Texture {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<u8>, // RGBA
+}
+
+impl Texture {
+    pub fn from_image(image: &RgbaImage) -> Self {
+        let (width, height) = image.dimensions();
+        Self { width, height, pixels: image.as_raw().clone() }
+    }
+
+    // Muestrea con coordenadas normalizadas 0..1 (vecino más cercano)
+    pub fn sample(&self, u: f32, v: f32) -> [u8; 3] {
+        let x = ((u * self.width as f32) as u32).min(self.width - 1);
+        let y = ((v * self.height as f32) as u32).min(self.height - 1);
+        let idx = ((y * self.width + x) * 4) as usize;
+        [self.pixels[idx], self.pixels[idx + 1], self.pixels[idx + 2]]
+    }
+}
+
+// Sin texturas (`default`) todo se dibuja con colores planos
+#[derive(Default)]
+pub struct TextureAtlas {
+    walls: HashMap<u8, Texture>,
+    floor: Option<Texture>,
+    ceiling: Option<Texture>,
+}
+
+impl TextureAtlas {
+    // Toma las texturas ya cargadas; las que falten se dibujarán con color plano
+    pub fn from_assets(assets: &Assets) -> Self {
+        let texture = |id| assets.image(id).map(Texture::from_image);
+        let walls = (0..=u8::MAX)
+            .filter_map(|wall_type| Some((wall_type, texture(ImageId::wall(wall_type)?)?)))
+            .collect();
+        Self {
+            walls,
+            floor: texture(ImageId::Floor),
+            ceiling: texture(ImageId::Ceiling),
+        }
+    }
+
+    pub fn wall(&self, wall_type: u8) -> Option<&Texture> {
+        self.walls.get(&wall_type)
+    }
+
+    pub fn floor(&self) -> Option<&Texture> {
+        self.floor.as_ref()
+    }
+
+    pub fn ceiling(&self) -> Option<&Texture> {
+        self.ceiling.as_ref()
+    }
+}
@@ -0,0 +1,123 @@
+// Pruebas de regresión del dibujo: cada caso se dibuja fuera de pantalla y se
+// compara con una imagen de referencia en `tests/golden/`. Para regenerarlas
+// tras un cambio intencional: `UPDATE_GOLDEN=1 cargo test --test golden`
+use image::{Rgba, RgbaImage};
+use proyecto::assets::Assets;
+use proyecto::game::Game;
+use proyecto::map::generate::{generate, Algorithm};
+use proyecto::map::Map;
+use proyecto::render::render_to_image;
+use proyecto::textures::TextureAtlas;
+use std::path::{Path, PathBuf};
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 240;
+const TOLERANCE: u8 = 3; // diferencia máxima por canal
+const MAX_BAD_RATIO: f32 = 0.001; // fracción de píxeles que puede superarla
+
+struct Case {
+    name: &'static str,
+    map: fn() -> Map,
+    pose: (f32, f32, f32), // x, y, ángulo en grados
+    textured: bool,
+}
+
+fn cripta() -> Map {
+    Map::load("levels/cripta.txt").unwrap()
+}
+
+fn generated() -> Map {
+    generate(6, 5, Algorithm::Kruskal, 7)
+}
+
+fn dense_fog() -> Map {
+    "@fog exp 0.3\n@fog_color 200 200 210\n#########\n#S......#\n#.##2##.#\n#...3..E#\n#########\n".parse().unwrap()
+}
+
+const CASES: [Case; 6] = [
+    Case { name: "inicio_este", map: Map::new, pose: (1.5, 1.5, 0.0), textured: true },
+    Case { name: "inicio_sur_plano", map: Map::new, pose: (1.5, 1.5, 90.0), textured: false },
+    Case { name: "esquina_diagonal", map: Map::new, pose: (3.5, 3.5, 225.0), textured: true },
+    Case { name: "cripta", map: cripta, pose: (1.5, 1.5, 30.0), textured: true },
+    Case { name: "generado", map: generated, pose: (1.5, 1.5, 45.0), textured: true },
+    Case { name: "niebla_densa", map: dense_fog, pose: (1.2, 1.5, 10.0), textured: false },
+];
+
+fn render_case(case: &Case, textures: &TextureAtlas) -> RgbaImage {
+    let mut game = Game::new((case.map)());
+    let (x, y, angle) = case.pose;
+    game.player.x = x;
+    game.player.y = y;
+    game.player.angle = angle.to_radians();
+    let flat = TextureAtlas::default();
+    render_to_image(WIDTH, HEIGHT, &game, if case.textured { textures } else { &flat })
+}
+
+// Cuenta los píxeles que difieren más que la tolerancia y arma una imagen
+// con ellos en rojo sobre la versión atenuada de la esperada
+fn compare(expected: &RgbaImage, actual: &RgbaImage) -> (usize, RgbaImage) {
+    let mut diff = RgbaImage::new(actual.width(), actual.height());
+    let mut bad = 0;
+    for (x, y, a) in actual.enumerate_pixels() {
+        let e = expected.get_pixel(x, y);
+        let differs = (0..3).any(|c| a[c].abs_diff(e[c]) > TOLERANCE);
+        let pixel = if differs {
+            bad += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            Rgba([e[0] / 3, e[1] / 3, e[2] / 3, 255])
+        };
+        diff.put_pixel(x, y, pixel);
+    }
+    (bad, diff)
+}
+
+fn output_dir() -> PathBuf {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join("golden")
+}
+
+#[test]
+fn renders_match_reference_images() {
+    let textures = TextureAtlas::from_assets(&Assets::load(Some(Path::new("assets"))).0);
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let mut failures = Vec::new();
+    for case in &CASES {
+        let actual = render_case(case, &textures);
+        let reference = Path::new("tests/golden").join(format!("{}.png", case.name));
+        if update {
+            actual.save(&reference).unwrap();
+            continue;
+        }
+        let expected = match image::open(&reference) {
+            Ok(image) => image.to_rgba8(),
+            Err(e) => {
+                failures.push(format!("{}: no se pudo abrir la referencia: {}", case.name, e));
+                continue;
+            }
+        };
+        if expected.dimensions() != actual.dimensions() {
+            failures.push(format!("{}: tamaño {:?}, se esperaba {:?}", case.name, actual.dimensions(), expected.dimensions()));
+            continue;
+        }
+        let (bad, diff) = compare(&expected, &actual);
+        if bad as f32 > MAX_BAD_RATIO * (WIDTH * HEIGHT) as f32 {
+            let dir = output_dir();
+            std::fs::create_dir_all(&dir).unwrap();
+            actual.save(dir.join(format!("{}.actual.png", case.name))).unwrap();
+            diff.save(dir.join(format!("{}.diff.png", case.name))).unwrap();
+            failures.push(format!("{}: {} píxeles distintos (ver {})", case.name, bad, dir.display()));
+        }
+    }
+    assert!(failures.is_empty(), "imágenes distintas a las de referencia:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn comparison_detects_a_moved_camera() {
+    let textures = TextureAtlas::from_assets(&Assets::load(Some(Path::new("assets"))).0);
+    let case = &CASES[0];
+    let moved = Case { pose: (1.6, 1.5, 2.0), ..*case };
+    let (bad, _) = compare(&render_case(case, &textures), &render_case(&moved, &textures));
+    assert!(bad as f32 > MAX_BAD_RATIO * (WIDTH * HEIGHT) as f32);
+    let (same, _) = compare(&render_case(case, &textures), &render_case(case, &textures));
+    assert_eq!(same, 0);
+}
@@ -0,0 +1,43 @@
+// Pruebas del modo `--render` ejecutando el programa compilado, sin ventana
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn out_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join(name)
+}
+
+// Ejecuta una captura del laberinto integrado; `env` fija (o borra, si es
+// `None`) la variable con la carpeta de recursos
+fn render(args: &[&str], env: Option<&str>, out: &Path) -> Output {
+    let _ = std::fs::remove_file(out);
+    let mut command = Command::new(env!("CARGO_BIN_EXE_proyecto"));
+    command.current_dir(env!("CARGO_MANIFEST_DIR")).args(args);
+    command.args(["--render", "1.5,1.5,0", "--out"]).arg(out);
+    match env {
+        Some(value) => command.env("LABERINTO_ASSETS", value),
+        None => command.env_remove("LABERINTO_ASSETS"),
+    };
+    command.output().expect("no se pudo ejecutar el programa")
+}
+
+#[test]
+fn renders_with_the_assets_folder() {
+    let out = out_path("render_cli_ok.png");
+    let output = render(&["--assets", "assets"], None, &out);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(image::open(&out).unwrap().to_rgba8().dimensions(), (640, 480));
+}
+
+#[test]
+fn missing_explicit_assets_folder_is_an_error() {
+    for (args, env, name) in [
+        (&["--assets", "no_existe"][..], None, "render_cli_flag.png"),
+        (&[][..], Some("no_existe"), "render_cli_env.png"),
+    ] {
+        let out = out_path(name);
+        let output = render(args, env, &out);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("'no_existe'"));
+        assert!(!out.exists(), "no debería guardarse una captura sin texturas");
+    }
+}
@@ -0,0 +1,103 @@
+use proyecto::game::{Game, InputFrame, TIMESTEP};
+use proyecto::map::generate::{generate, Algorithm};
+use proyecto::map::Map;
+use std::f32::consts::{PI, TAU};
+
+const MAX_STEPS: u32 = 120 * 120; // dos minutos simulados
+
+// Piloto automático: gira hacia el centro de la siguiente celda del camino
+// más corto y avanza cuando la tiene casi de frente
+fn autopilot(game: &Game, path: &[(usize, usize)], next: &mut usize) -> InputFrame {
+    let player = &game.player;
+    let (cx, cy) = path[*next];
+    // La meta se mide desde la esquina de su celda, no desde el centro
+    let (tx, ty) = if *next + 1 == path.len() { (cx as f32, cy as f32) } else { (cx as f32 + 0.5, cy as f32 + 0.5) };
+    if (tx - player.x).hypot(ty - player.y) < 0.15 && *next + 1 < path.len() {
+        *next += 1;
+        return autopilot(game, path, next);
+    }
+    let wanted = (ty - player.y).atan2(tx - player.x);
+    let diff = (wanted - player.angle + PI).rem_euclid(TAU) - PI;
+    let turn = if diff.abs() > 0.03 { diff.signum() } else { 0.0 };
+    let forward = if diff.abs() < 0.3 { 1.0 } else { 0.0 };
+    InputFrame { forward, turn, ..Default::default() }
+}
+
+// Juega una partida completa a paso fijo; devuelve los pasos usados
+fn solve(game: &mut Game) -> u32 {
+    let path = game.map.shortest_path().expect("el laberinto tiene solución").cells;
+    let mut next = 0;
+    for steps in 1..=MAX_STEPS {
+        let input = autopilot(game, &path, &mut next);
+        game.step(input, TIMESTEP);
+        if game.is_finished() {
+            return steps;
+        }
+    }
+    panic!("no llegó a la meta; quedó en ({}, {})", game.player.x, game.player.y);
+}
+
+#[test]
+fn autopilot_finishes_builtin_maze() {
+    let mut game = Game::new(Map::new());
+    let steps = solve(&mut game);
+    let stats = &game.stats;
+    assert_eq!(stats.finished_time, Some(game.elapsed()));
+    assert!((game.elapsed().as_secs_f32() - steps as f32 * TIMESTEP).abs() < 1e-3);
+    let optimal = game.map.shortest_path().unwrap().steps();
+    // El piloto corta un poco las esquinas pero sigue el camino óptimo
+    let ratio = stats.distance / optimal as f32;
+    assert!((0.9..1.1).contains(&ratio), "recorrió {} para un camino de {}", stats.distance, optimal);
+    assert!(stats.turns > 0);
+}
+
+#[test]
+fn same_inputs_give_same_run() {
+    let map = generate(8, 6, Algorithm::Prim, 42);
+    let mut a = Game::new(map.clone());
+    let mut b = Game::new(map);
+    assert_eq!(solve(&mut a), solve(&mut b));
+    assert_eq!(a.player, b.player);
+    assert_eq!(a.stats, b.stats);
+}
+
+#[test]
+fn frame_rate_does_not_change_the_simulation() {
+    // Las mismas entradas repartidas en cuadros de distinta duración
+    let inputs = [
+        InputFrame { forward: 1.0, ..Default::default() },
+        InputFrame { forward: 1.0, turn: 1.0, ..Default::default() },
+        InputFrame { strafe: -1.0, ..Default::default() },
+    ];
+    let run = |frame_dt: f32| {
+        let mut game = Game::new(Map::new());
+        for input in inputs {
+            let mut left = 1.0;
+            while left > 1e-4 {
+                let dt = frame_dt.min(left);
+                game.advance(input, dt);
+                left -= dt;
+            }
+        }
+        game
+    };
+    let (slow, fast) = (run(1.0 / 30.0), run(1.0 / 240.0));
+    assert!((slow.player.x - fast.player.x).abs() < 0.05);
+    assert!((slow.player.y - fast.player.y).abs() < 0.05);
+    assert!((slow.player.angle - fast.player.angle).abs() < 0.05);
+}
+
+#[test]
+fn finished_run_ignores_input() {
+    let mut game = Game::new(Map::new());
+    solve(&mut game);
+    let (player, stats) = (game.player.clone(), game.stats.clone());
+    game.advance(InputFrame { forward: 1.0, turn: 1.0, ..Default::default() }, 1.0);
+    assert_eq!(game.player, player);
+    assert_eq!(game.stats, stats);
+
+    game.reset();
+    assert!(!game.is_finished());
+    assert_eq!(game.elapsed().as_secs_f32(), 0.0);
+    assert_eq!((game.player.x, game.player.y), game.map.start);
+}